@@ -0,0 +1,20 @@
+//! Records the version of the compiler that builds the crate, so benchmarks report the toolchain
+//! that compiled the solutions rather than whichever `rustc` is on `PATH` at bench time.
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=RUSTC");
+
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let version = Command::new(rustc)
+        .arg("-V")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+
+    if let Some(version) = version.filter(|version| !version.is_empty()) {
+        println!("cargo:rustc-env=AOC_RUSTC_VERSION={version}");
+    }
+}
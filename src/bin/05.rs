@@ -1,47 +1,41 @@
 advent_of_code::solution!(5);
+use advent_of_code::graph::{Graph, NodeId};
+use advent_of_code::parse::{key_values, lines_with, sections_with_lines, uints, ParseError};
 use std::collections::HashMap;
+use std::num::ParseIntError;
 
-fn parse(input: &str) -> (String, String) {
-    let mut rules = String::new();
-    let mut updates = String::new();
-    let mut found_empty = false;
-    for line in input.lines() {
-        if line.is_empty() {
-            found_empty = true;
-        }
-        if !found_empty {
-            rules = format!("{}\n{}", rules, line);
-        } else {
-            updates = format!("{}\n{}", updates, line);
-        }
-    }
-    (rules.trim().to_owned(), updates.trim().to_owned())
+/// Maps every page to the pages which have to come before it.
+type Rules = HashMap<u32, Vec<u32>>;
+
+/// The ordering rules and the updates.
+fn parse(input: &str) -> Result<(Rules, Vec<Vec<u32>>), ParseError> {
+    let mut sections = sections_with_lines(input);
+    let (rules_line, rules) = sections.next().unwrap_or((1, ""));
+    let (updates_line, updates) = sections.next().unwrap_or((1, ""));
+    let updates = lines_with(updates, parse_update).map_err(|e| e.in_section(updates_line))?;
+    Ok((parse_hash_map(rules, rules_line)?, updates))
 }
 
-fn parse_hash_map(rules: String) -> HashMap<u32, Vec<u32>> {
-    let mut before: HashMap<u32, Vec<u32>> = HashMap::new(); // maps to pages which are before the key
-    rules
-        .lines()
-        .map(|line| {
-            let rule: Vec<&str> = line.split('|').collect();
-            (
-                rule[0].parse::<u32>().unwrap(),
-                rule[1].parse::<u32>().unwrap(),
-            )
-        })
-        .for_each(|(prev, after)| {
-            before
-                .entry(after)
-                .and_modify(|vec| vec.push(prev))
-                .or_insert(vec![prev]);
-        });
-    before
+/// `first_line` is the line of the rules in the input, so errors point at the right line.
+fn parse_hash_map(rules: &str, first_line: usize) -> Result<Rules, ParseError> {
+    let mut before: Rules = HashMap::new();
+    let rules = key_values(rules, "|").map_err(|e| e.in_section(first_line))?;
+    for (i, (prev, after)) in rules.into_iter().enumerate() {
+        let page = |page: &str| {
+            page.parse::<u32>()
+                .map_err(|e| ParseError::new(first_line + i, format!("{page:?}: {e}")))
+        };
+        let (prev, after) = (page(prev)?, page(after)?);
+        before
+            .entry(after)
+            .and_modify(|vec| vec.push(prev))
+            .or_insert(vec![prev]);
+    }
+    Ok(before)
 }
-fn parse_update(update: &str) -> Vec<u32> {
-    update
-        .split(',')
-        .map(|number| number.parse().unwrap())
-        .collect::<Vec<u32>>()
+
+fn parse_update(update: &str) -> Result<Vec<u32>, ParseIntError> {
+    uints(update).collect()
 }
 
 fn check_update(update: &Vec<u32>, before: &HashMap<u32, Vec<u32>>) -> bool {
@@ -59,29 +53,31 @@ fn check_update(update: &Vec<u32>, before: &HashMap<u32, Vec<u32>>) -> bool {
     true
 }
 
-fn order_update(update: Vec<u32>, before: &HashMap<u32, Vec<u32>>) -> Vec<u32> {
-    let mut update = update
-        .iter()
-        .map(|page| {
-            (
-                *page,
-                before
-                    .iter()
-                    .filter(|(key, vec)| update.contains(key) && vec.contains(page))
-                    .count(),
-            )
-        })
-        .collect::<Vec<(u32, usize)>>();
-    update.sort_by(|(_, a), (_, b)| a.cmp(b).reverse());
-    update.iter().map(|(key, _)| *key).collect::<Vec<u32>>()
+/// Orders the pages by the rules, [`None`] if the rules contradict each other.
+fn order_update(update: Vec<u32>, before: &Rules) -> Option<Vec<u32>> {
+    // node `i` is the page `update[i]`.
+    let mut edges = vec![];
+    for (i, page) in update.iter().enumerate() {
+        for (j, prev) in update.iter().enumerate() {
+            if before
+                .get(page)
+                .is_some_and(|previous| previous.contains(prev))
+            {
+                edges.push((j as NodeId, i as NodeId));
+            }
+        }
+    }
+
+    let order = Graph::from_ids(true, update.len(), edges)
+        .topological_sort()
+        .ok()?;
+    Some(order.into_iter().map(|id| update[id as usize]).collect())
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let (rules, updates) = parse(input);
-    let before = parse_hash_map(rules);
+    let (before, updates) = parse(input).ok()?;
     let result = updates
-        .lines()
-        .map(|update| parse_update(update))
+        .into_iter()
         .filter(|update| check_update(update, &before))
         .map(|update| update[update.len() / 2])
         .sum();
@@ -89,16 +85,14 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let (rules, updates) = parse(input);
-    let before = parse_hash_map(rules);
-    let result = updates
-        .lines()
-        .map(|update| parse_update(update))
+    let (before, updates) = parse(input).ok()?;
+    // NOTE: summing options gives `None` as soon as one update cannot be ordered.
+    updates
+        .into_iter()
         .filter(|update| !check_update(update, &before))
         .map(|update| order_update(update, &before))
-        .map(|update| update[update.len() / 2])
-        .sum();
-    Some(result)
+        .map(|update| update.map(|update| update[update.len() / 2]))
+        .sum()
 }
 
 #[cfg(test)]
@@ -116,4 +110,9 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(123));
     }
+
+    #[test]
+    fn test_contradicting_rules() {
+        assert_eq!(part_two("1|2\n2|3\n3|1\n\n1,2,3"), None);
+    }
 }
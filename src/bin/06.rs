@@ -1,188 +1,53 @@
 advent_of_code::solution!(6);
 
-use Direction::*;
-
-#[derive(Clone)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
-}
-
-impl Direction {
-    fn turn_right(&self) -> Self {
-        match *self {
-            Up => Right,
-            Right => Down,
-            Down => Left,
-            Left => Up,
-        }
-    }
-
-    fn next_position(
-        &self,
-        position: (usize, usize),
-        max_rows: usize,
-        max_columns: usize,
-    ) -> Option<(usize, usize)> {
-        match *self {
-            Up => Some((position.0.checked_sub(1)?, position.1)),
-            Right => {
-                if position.1 + 1 < max_columns {
-                    Some((position.0, position.1 + 1))
-                } else {
-                    None
-                }
-            }
-            Down => {
-                if position.0 + 1 < max_rows {
-                    Some((position.0 + 1, position.1))
-                } else {
-                    None
-                }
-            }
-            Left => Some((position.0, position.1.checked_sub(1)?)),
-        }
+use std::collections::HashSet;
+
+use advent_of_code::cycle::try_find_cycle;
+use advent_of_code::{Dir4, Grid};
+
+type Guard = ((usize, usize), Dir4);
+
+/// Moves the guard one step, turning right in front of obstacles.
+/// Returns [`None`] once the guard leaves the map.
+fn step(map: &Grid<char>, obstacle: Option<(usize, usize)>, guard: &Guard) -> Option<Guard> {
+    let (position, direction) = *guard;
+    let next = direction.next_position(position, map.height(), map.width())?;
+    if map[next] == '#' || Some(next) == obstacle {
+        Some((position, direction.turn_right()))
+    } else {
+        Some((next, direction))
     }
 }
 
-#[derive(Clone)]
-struct Guard {
-    direction: Direction,
-    position: (usize, usize),
-    unique_places_checked: u32,
-    finished: bool,
-    is_stuck: bool,
-}
-
-impl Guard {
-    fn move_next_position(mut self, map: &mut Vec<Vec<char>>) -> Self {
-        if let Some((row, col)) =
-            self.direction
-                .next_position(self.position, map.len(), map[0].len())
-        {
-            match map[row][col] {
-                '#' => {
-                    self.direction = self.direction.turn_right();
-                    self
-                }
-                '.' => {
-                    map[row][col] = 'X';
-                    self.position = (row, col);
-                    self.unique_places_checked += 1;
-                    self
-                }
-                'X' | '>' | 'V' | '<' | '^' => {
-                    map[row][col] = 'W';
-                    self.position = (row, col);
-                    self
-                }
-                'W' => {
-                    map[row][col] = 'Q';
-                    self.position = (row, col);
-                    self
-                }
-                'Q' => {
-                    self.is_stuck = true;
-                    self
-                }
-                _ => unreachable!("There was an unexpected symbol on the map"),
-            }
-        } else {
-            self.finished = true;
-            self
-        }
+fn patrol(map: &Grid<char>, start: Guard) -> HashSet<(usize, usize)> {
+    let mut visited = HashSet::from([start.0]);
+    let mut guard = start;
+    while let Some(next) = step(map, None, &guard) {
+        visited.insert(next.0);
+        guard = next;
     }
+    visited
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let mut map = input
-        .lines()
-        .map(|line| line.chars().collect())
-        .collect::<Vec<Vec<char>>>();
-    let mut guard = Guard {
-        direction: Up,
-        position: (0, 0),
-        unique_places_checked: 1,
-        finished: false,
-        is_stuck: false,
-    };
-    for (i, row) in map.iter().enumerate() {
-        if row.contains(&'^') {
-            for (j, &char) in row.iter().enumerate() {
-                if char == '^' {
-                    guard.position = (i, j)
-                }
-            }
-        }
-    }
-    while !guard.finished {
-        guard = guard.move_next_position(&mut map)
-    }
-    Some(guard.unique_places_checked)
+    let map: Grid<char> = input.parse().ok()?;
+    let start = (map.find(&'^')?, Dir4::Up);
+    Some(patrol(&map, start).len() as u32)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let init_map = input
-        .lines()
-        .map(|line| line.chars().collect())
-        .collect::<Vec<Vec<char>>>();
-    let mut init_guard = Guard {
-        direction: Up,
-        position: (0, 0),
-        unique_places_checked: 1,
-        finished: false,
-        is_stuck: false,
-    };
-    let mut unique_obstacles = 0;
-
-    for (i, row) in init_map.iter().enumerate() {
-        if row.contains(&'^') {
-            for (j, &char) in row.iter().enumerate() {
-                if char == '^' {
-                    init_guard.position = (i, j)
-                }
-            }
-        }
-    }
-
-    let mut guard = init_guard.clone();
-    let mut traversed_map = init_map.clone();
-    while !guard.finished {
-        guard = guard.move_next_position(&mut traversed_map)
-    }
-
-    for (i, row) in traversed_map.clone().iter().enumerate() {
-        for (j, &char) in row.iter().enumerate() {
-            if (char == 'X' || char == 'W' || char == 'Q')
-                && !(i == init_guard.position.0 && j == init_guard.position.1)
-            {
-                let mut guard = init_guard.clone();
-                let mut map = init_map.clone();
-
-                map[i][j] = '#';
-                while !guard.finished {
-                    guard = guard.move_next_position(&mut map);
-                    if guard.is_stuck {
-                        unique_obstacles += 1;
-                        break;
-                    }
-                }
-            }
-        }
-    }
-    Some(unique_obstacles)
-}
-
-fn _print_map(map: &Vec<Vec<char>>) {
-    for row in map {
-        for c in row {
-            print!("{c}");
-        }
-        println!("");
-    }
-    println!("");
+    let map: Grid<char> = input.parse().ok()?;
+    let start = (map.find(&'^')?, Dir4::Up);
+
+    // only obstacles on the original route can change it.
+    let loops = patrol(&map, start)
+        .into_iter()
+        .filter(|&position| position != start.0)
+        .filter(|&position| {
+            try_find_cycle(start, |guard| step(&map, Some(position), guard)).is_some()
+        })
+        .count();
+    Some(loops as u32)
 }
 
 #[cfg(test)]
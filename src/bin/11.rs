@@ -1,5 +1,5 @@
 advent_of_code::solution!(11);
-use std::collections::HashMap;
+use advent_of_code::counter::Counter;
 
 #[inline]
 fn num_digits(n: u64) -> u32 {
@@ -27,22 +27,11 @@ fn blink(stone: u64) -> Vec<u64> {
     }
 }
 
-fn blink_n_times(stones: &Vec<u64>, blinks: u64, cache: &mut HashMap<(u64, u64), u64>) -> u64 {
-    if blinks == 0 {
-        stones.len() as u64
-    } else {
-        stones
-            .iter()
-            .map(|stone| match cache.get_key_value(&(*stone, blinks)) {
-                Some((_, value)) => *value,
-                None => {
-                    let stones = blink(*stone);
-                    let lenght = blink_n_times(&stones, blinks - 1, cache);
-                    *cache.entry((*stone, blinks)).or_insert(lenght)
-                }
-            })
-            .sum()
-    }
+/// Stones never influence each other, so only the number of stones per engraving matters.
+fn blink_n_times(stones: &[u64], blinks: usize) -> u64 {
+    let mut stones: Counter<u64> = stones.iter().copied().collect();
+    stones.evolve_n(blinks, |&stone| blink(stone));
+    stones.total()
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
@@ -50,8 +39,7 @@ pub fn part_one(input: &str) -> Option<u64> {
         .split(' ')
         .map(|number| number.trim().parse::<u64>().unwrap())
         .collect::<Vec<u64>>();
-    let mut cache: HashMap<(u64, u64), u64> = HashMap::new();
-    Some(blink_n_times(&initial_stones, 25, &mut cache))
+    Some(blink_n_times(&initial_stones, 25))
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
@@ -59,8 +47,7 @@ pub fn part_two(input: &str) -> Option<u64> {
         .split(' ')
         .map(|number| number.trim().parse::<u64>().unwrap())
         .collect::<Vec<u64>>();
-    let mut cache: HashMap<(u64, u64), u64> = HashMap::new();
-    Some(blink_n_times(&initial_stones, 75, &mut cache))
+    Some(blink_n_times(&initial_stones, 75))
 }
 
 #[cfg(test)]
@@ -73,8 +60,7 @@ mod tests {
             .split(' ')
             .map(|number| number.trim().parse::<u64>().unwrap())
             .collect::<Vec<u64>>();
-        let mut cache: HashMap<(u64, u64), u64> = HashMap::new();
-        let result = blink_n_times(&initial_stones, 25, &mut cache);
+        let result = blink_n_times(&initial_stones, 25);
         assert_eq!(result, 55312);
     }
 }
@@ -1,8 +1,11 @@
 advent_of_code::solution!(13);
 
-#[derive(Debug, Default)]
+use advent_of_code::math::{extended_gcd, solve_linear, Solutions};
+use advent_of_code::parse::{ints_array, sections_with_lines, ParseError};
+
+#[derive(Debug, Default, Clone, Copy)]
 struct Equation {
-    // according to Cramer's rule
+    // b * A + a * B = e and d * A + c * B = f
     a: i64,
     b: i64,
     c: i64,
@@ -12,143 +15,76 @@ struct Equation {
 }
 
 impl Equation {
-    fn parse_button_a(&mut self, input: &str) {
-        input
-            .chars()
-            .filter(|char| char.is_ascii_digit() || *char == ',')
-            .collect::<String>()
-            .split(',')
-            .enumerate()
-            .map(|(i, value)| {
-                if i == 0 {
-                    self.b = value.parse().unwrap()
-                } else {
-                    self.d = value.parse().unwrap()
-                }
-            })
-            .count();
-    }
-
-    fn parse_button_b(&mut self, input: &str) {
-        input
-            .chars()
-            .filter(|char| char.is_ascii_digit() || *char == ',')
-            .collect::<String>()
-            .split(',')
-            .enumerate()
-            .map(|(i, value)| {
-                if i == 0 {
-                    self.a = value.parse().unwrap()
-                } else {
-                    self.c = value.parse().unwrap()
-                }
-            })
-            .count();
-    }
-
-    fn parse_prizes(&mut self, input: &str) {
-        input
-            .chars()
-            .filter(|char| char.is_ascii_digit() || *char == ',')
-            .collect::<String>()
-            .split(',')
-            .enumerate()
-            .map(|(i, value)| {
-                if i == 0 {
-                    self.e = value.parse().unwrap()
-                } else {
-                    self.f = value.parse().unwrap()
-                }
-            })
-            .count();
-    }
-
-    fn parse_prizes_part_two(&mut self, input: &str) {
-        input
-            .chars()
-            .filter(|char| char.is_ascii_digit() || *char == ',')
-            .collect::<String>()
-            .split(',')
-            .enumerate()
-            .map(|(i, value)| {
-                if i == 0 {
-                    self.e = value.parse::<i64>().unwrap() + 10_000_000_000_000_i64
-                } else {
-                    self.f = value.parse::<i64>().unwrap() + 10_000_000_000_000_i64
-                }
-            })
-            .count();
-    }
-
-    #[inline]
-    fn det_a(&self) -> i64 {
-        self.a * self.d - self.b * self.c
-    }
-
-    #[inline]
-    fn det_a_x(&self) -> i64 {
-        self.a * self.f - self.e * self.c
+    /// Parses the three lines `Button A: X+94, Y+34`, `Button B: X+22, Y+67` and
+    /// `Prize: X=8400, Y=5400`.
+    fn parse(section: &str) -> Option<Self> {
+        let [b, d, a, c, e, f] = ints_array(section)?;
+        Some(Equation { a, b, c, d, e, f })
     }
 
-    #[inline]
-    fn det_a_y(&self) -> i64 {
-        self.e * self.d - self.b * self.f
+    /// How many times to press buttons A and B, the cheapest way if there are many.
+    fn solve(&self) -> Option<(i64, i64)> {
+        let matrix = [vec![self.b, self.a], vec![self.d, self.c]];
+        match solve_linear(&matrix, &[self.e, self.f]) {
+            Solutions::None => None,
+            Solutions::One(presses) => Some((
+                presses[0].to_integer()?.try_into().ok()?,
+                presses[1].to_integer()?.try_into().ok()?,
+            )),
+            Solutions::Infinite { .. } => self.solve_collinear(),
+        }
     }
 
-    #[inline]
-    // How many times to press Button A
-    fn solve_for_a(&self) -> Option<i64> {
-        let det_a = self.det_a();
-        if det_a != 0 {
-            let det_a_x = self.det_a_x();
-            if det_a_x % det_a == 0 {
-                Some(det_a_x / det_a)
-            } else {
-                None
-            }
+    /// Both buttons move along the same line, so a single equation `x_a * A + x_b * B = target`
+    /// constrains the presses. Its integer solutions are `(A0 + k * x_b / g, B0 - k * x_a / g)`.
+    fn solve_collinear(&self) -> Option<(i64, i64)> {
+        let (x_a, x_b, target) = if self.b != 0 || self.a != 0 {
+            (self.b, self.a, self.e)
         } else {
-            // TODO extend with infinitely many solutions case if needed
-            None // No solution case
+            (self.d, self.c, self.f)
+        };
+        if x_b == 0 {
+            return (x_a != 0 && target % x_a == 0).then(|| (target / x_a, 0));
+        }
+        if x_a == 0 {
+            return (target % x_b == 0).then(|| (0, target / x_b));
         }
-    }
 
-    #[inline]
-    // How many times to press Button B
-    fn solve_for_b(&self) -> Option<i64> {
-        let det_a = self.det_a();
-        if det_a != 0 {
-            let det_a_y = self.det_a_y();
-            if det_a_y % det_a == 0 {
-                Some(self.det_a_y() / det_a)
-            } else {
-                None
-            }
-        } else {
-            // TODO extend with infinitely many solutions case if needed
-            None // No solution case
+        let (g, p, q) = extended_gcd(x_a, x_b);
+        if target % g != 0 {
+            return None;
         }
+        let (a0, b0) = (p * (target / g), q * (target / g));
+        let (step_a, step_b) = (x_b / g, x_a / g);
+
+        // keep both press counts non-negative.
+        let min_k = -a0.div_euclid(step_a);
+        let max_k = b0.div_euclid(step_b);
+        if min_k > max_k {
+            return None;
+        }
+
+        // the cost changes linearly in k, so the cheapest solution is at one end of the range.
+        let k = if 3 * step_a >= step_b { min_k } else { max_k };
+        Some((a0 + k * step_a, b0 - k * step_b))
     }
 }
 
+fn parse(input: &str) -> Result<Vec<Equation>, ParseError> {
+    sections_with_lines(input)
+        .map(|(line, section)| {
+            Equation::parse(section)
+                .ok_or_else(|| ParseError::new(line, "expected two buttons and a prize"))
+        })
+        .collect()
+}
+
 pub fn part_one(input: &str) -> Option<u64> {
-    let mut equations = vec![];
-    let mut equation = Equation::default();
-    for (i, line) in input.lines().filter(|line| !line.is_empty()).enumerate() {
-        match i % 3 {
-            0 => equation.parse_button_a(line),
-            1 => equation.parse_button_b(line),
-            2 => {
-                equation.parse_prizes(line);
-                equations.push(equation);
-                equation = Equation::default();
-            }
-            _ => unreachable!("Should not be possible"),
-        }
-    }
+    let equations = parse(input).ok()?;
     Some(
         equations
             .iter()
-            .filter_map(|equation| Some((equation.solve_for_a()?, equation.solve_for_b()?)))
+            .filter_map(|equation| equation.solve())
             .filter(|(a, b)| *a <= 100 && *a >= 0 && *b <= 100 && *b >= 0)
             .map(|(a, b)| (a * 3 + b) as u64)
             .sum::<u64>(),
@@ -156,24 +92,16 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let mut equations = vec![];
-    let mut equation = Equation::default();
-    for (i, line) in input.lines().filter(|line| !line.is_empty()).enumerate() {
-        match i % 3 {
-            0 => equation.parse_button_a(line),
-            1 => equation.parse_button_b(line),
-            2 => {
-                equation.parse_prizes_part_two(line);
-                equations.push(equation);
-                equation = Equation::default();
-            }
-            _ => unreachable!("Should not be possible"),
-        }
-    }
+    let equations = parse(input).ok()?;
     Some(
         equations
             .iter()
-            .filter_map(|equation| Some((equation.solve_for_a()?, equation.solve_for_b()?)))
+            .map(|&equation| Equation {
+                e: equation.e + 10_000_000_000_000,
+                f: equation.f + 10_000_000_000_000,
+                ..equation
+            })
+            .filter_map(|equation| equation.solve())
             .filter(|(a, b)| *a >= 0 && *b >= 0)
             .map(|(a, b)| (a * 3 + b) as u64)
             .sum::<u64>(),
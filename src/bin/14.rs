@@ -1,140 +1,122 @@
 advent_of_code::solution!(14);
-use std::thread;
-use std::time::Duration;
-
-type Coordinates = (usize, usize);
-type Velocity = (i32, i32);
+use advent_of_code::math::crt;
+use advent_of_code::parse::{ints_array, lines_as};
+use advent_of_code::Vec2;
 
 #[derive(Debug)]
 struct Guard {
-    coordinates: Coordinates,
-    velocity: Velocity,
+    position: Vec2,
+    velocity: Vec2,
 }
 
-trait VelocityMul {
-    fn mul(self, rhs: usize) -> Self;
+impl Guard {
+    fn move_n_secs(&mut self, n: usize, map_size: Vec2) {
+        self.position = self.position_after(n as i64, map_size);
+    }
+
+    fn position_after(&self, secs: i64, map_size: Vec2) -> Vec2 {
+        (self.position + self.velocity * secs).rem_euclid(map_size)
+    }
 }
-impl VelocityMul for Velocity {
-    fn mul(self, rhs: usize) -> Self {
-        let x = self.0 * rhs as i32;
-        let y = self.1 * rhs as i32;
-        (x, y)
+
+/// The example uses an 11x7 map, real inputs use 101x103.
+fn map_size(guards: &[Guard]) -> Vec2 {
+    if guards
+        .iter()
+        .all(|guard| guard.position.x < 11 && guard.position.y < 7)
+    {
+        Vec2::new(11, 7)
+    } else {
+        Vec2::new(101, 103)
     }
 }
 
-impl Guard {
-    fn move_n_secs(&mut self, n: usize, map_size: Coordinates) {
-        if n == 0 {
-            return;
-        }
-        let coordinates = (self.coordinates.0 as i32, self.coordinates.1 as i32);
-        let map_size = (map_size.0 as i32, map_size.1 as i32);
-        let vector = self.velocity.mul(n);
-
-        let absolute_x = vector.0 + coordinates.0;
-        let absolute_y = vector.1 + coordinates.1;
-
-        let x = if absolute_x >= 0 {
-            absolute_x % map_size.0
-        } else {
-            (map_size.0 - (-absolute_x % map_size.0)) % map_size.0
-        };
+fn safety_factor(guards: &mut [Guard], secs: usize, map_size: Vec2) -> u32 {
+    let middle = Vec2::new(map_size.x / 2, map_size.y / 2);
+    let mut first_quadrant = 0;
+    let mut second_quadrant = 0;
+    let mut third_quadrant = 0;
+    let mut fourth_quadrant = 0;
+    guards
+        .iter_mut()
+        .map(|guard| {
+            guard.move_n_secs(secs, map_size);
+            guard.position
+        })
+        .for_each(|position| match (position.y, position.x) {
+            (row, col) if row < middle.y && col < middle.x => first_quadrant += 1,
+            (row, col) if row < middle.y && col > middle.x => second_quadrant += 1,
+            (row, col) if row > middle.y && col < middle.x => third_quadrant += 1,
+            (row, col) if row > middle.y && col > middle.x => fourth_quadrant += 1,
+            _ => {}
+        });
+    first_quadrant * second_quadrant * third_quadrant * fourth_quadrant
+}
 
-        let y = if absolute_y >= 0 {
-            absolute_y % map_size.1
-        } else {
-            (map_size.1 - (-absolute_y % map_size.1)) % map_size.1
-        };
-        self.coordinates = (x as usize, y as usize)
-    }
+/// The second within one `period` at which the `coordinate` of all guards varies the least.
+/// [`None`] if that variance does not clearly stand out, i.e. the guards never cluster.
+fn clustered_second(
+    guards: &[Guard],
+    period: i64,
+    coordinate: impl Fn(Vec2) -> i64,
+    map_size: Vec2,
+) -> Option<i64> {
+    let n = guards.len() as f64;
+    let variances: Vec<f64> = (0..period)
+        .map(|secs| {
+            let values: Vec<f64> = guards
+                .iter()
+                .map(|guard| coordinate(guard.position_after(secs, map_size)) as f64)
+                .collect();
+            let mean = values.iter().sum::<f64>() / n;
+            values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n
+        })
+        .collect();
+
+    // random seconds stay within a few standard deviations, the picture lies far outside.
+    let average = variances.iter().sum::<f64>() / period as f64;
+    let deviation =
+        (variances.iter().map(|v| (v - average).powi(2)).sum::<f64>() / period as f64).sqrt();
+    let (secs, &lowest) = variances
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))?;
+    (lowest < average - 4.0 * deviation).then_some(secs as i64)
+}
+
+/// The positions repeat every `width` seconds horizontally and every `height` seconds
+/// vertically. The picture is where both axes cluster at once, which the CRT combines.
+fn find_tree(guards: &[Guard], map_size: Vec2) -> Option<u32> {
+    let x = clustered_second(guards, map_size.x, |position| position.x, map_size)?;
+    let y = clustered_second(guards, map_size.y, |position| position.y, map_size)?;
+    let (secs, _) = crt(&[(x, map_size.x), (y, map_size.y)])?;
+    Some(secs as u32)
 }
 
 impl std::str::FromStr for Guard {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<i32> = s
-            .split(' ')
-            .flat_map(|half| {
-                half.chars()
-                    .filter(|char| char.is_ascii_digit() || *char == ',' || *char == '-')
-                    .collect::<String>()
-                    .split(',')
-                    .map(|number| number.parse().unwrap())
-                    .collect::<Vec<i32>>()
-            })
-            .collect();
-        if parts.len() != 4 {
+        let Some([px, py, vx, vy]) = ints_array(s) else {
             return Err("Input does not match the expected format".to_string());
-        }
+        };
 
         Ok(Guard {
-            coordinates: (parts[1] as usize, parts[0] as usize),
-            velocity: (parts[3], parts[2]),
+            position: Vec2::new(px, py),
+            velocity: Vec2::new(vx, vy),
         })
     }
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let mut guards: Vec<Guard> = input
-        .lines()
-        .map(|line| line.parse::<Guard>().unwrap())
-        .collect();
-    let mut first_quadrant = 0;
-    let mut second_quadrant = 0;
-    let mut third_quadrant = 0;
-    let mut fourth_quadrant = 0;
-    guards
-        .iter_mut()
-        .map(|guard| {
-            let secs = 100;
-            guard.move_n_secs(secs, (103, 101)); // you should change that
-            guard.coordinates
-        })
-        .for_each(|coordinates| match coordinates {
-            (x, y) if x < 51 && y < 50 => first_quadrant += 1,
-            (x, y) if x < 51 && y > 50 => second_quadrant += 1,
-            (x, y) if x > 51 && y < 50 => third_quadrant += 1,
-            (x, y) if x > 51 && y > 50 => fourth_quadrant += 1,
-            _ => {}
-        });
-    Some(first_quadrant * second_quadrant * third_quadrant * fourth_quadrant)
+    let mut guards: Vec<Guard> = lines_as(input).ok()?;
+    let map_size = map_size(&guards);
+    Some(safety_factor(&mut guards, 100, map_size))
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    // every 103 there was some kind of pattern
-    for n in (7603..=7603).step_by(103) {
-        let mut guards: Vec<Guard> = input
-            .lines()
-            .map(|line| line.parse::<Guard>().unwrap())
-            .collect();
-        let _end_coordinates = guards
-            .iter_mut()
-            .map(|guard| {
-                // let secs = 100;
-                guard.move_n_secs(n, (103, 101));
-                guard.coordinates
-            })
-            .collect::<Vec<(usize, usize)>>();
-
-        // println!("Seconds elapsed: {n}");
-        // for i in 0..103 {
-        //     for j in 0..101 {
-        //         let count = _end_coordinates
-        //             .iter()
-        //             .filter(|(row, column)| i == *row && j == *column)
-        //             .count() as u8;
-        //         if count == 0 {
-        //             print!(".");
-        //         } else {
-        //             print!("{count}");
-        //         }
-        //     }
-        //     println!("");
-        // }
-        thread::sleep(Duration::from_millis(500))
-    }
-    Some(7603)
+    let guards: Vec<Guard> = lines_as(input).ok()?;
+    find_tree(&guards, map_size(&guards))
 }
 
 #[cfg(test)]
@@ -152,4 +134,30 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn test_part_two_finds_tree() {
+        // 500 guards at random, 300 of them inside a 31x33 frame after `tree` seconds.
+        let (size, tree) = (Vec2::new(101, 103), 6_577);
+        let mut state: u64 = 0x1414_1414;
+        let mut random = |max: i64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % max as u64) as i64
+        };
+        let input: Vec<String> = (0..500)
+            .map(|i| {
+                let at_tree = if i < 300 {
+                    Vec2::new(35 + random(31), 40 + random(33))
+                } else {
+                    Vec2::new(random(101), random(103))
+                };
+                let velocity = Vec2::new(random(201) - 100, random(207) - 103);
+                let start = (at_tree - velocity * tree).rem_euclid(size);
+                format!("p={},{} v={},{}", start.x, start.y, velocity.x, velocity.y)
+            })
+            .collect();
+        assert_eq!(part_two(&input.join("\n")), Some(tree as u32));
+    }
 }
@@ -1,42 +1,8 @@
 advent_of_code::solution!(15);
 
-use Direction::*;
-
-#[derive(Clone, Debug, PartialEq, Eq)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
-}
-
-impl Direction {
-    fn next_position(
-        &self,
-        position: (usize, usize),
-        max_rows: usize,
-        max_columns: usize,
-    ) -> Option<(usize, usize)> {
-        match *self {
-            Up => Some((position.0.checked_sub(1)?, position.1)),
-            Right => {
-                if position.1 + 1 < max_columns {
-                    Some((position.0, position.1 + 1))
-                } else {
-                    None
-                }
-            }
-            Down => {
-                if position.0 + 1 < max_rows {
-                    Some((position.0 + 1, position.1))
-                } else {
-                    None
-                }
-            }
-            Left => Some((position.0, position.1.checked_sub(1)?)),
-        }
-    }
-}
+use advent_of_code::parse::sections;
+use advent_of_code::Dir4;
+use Dir4::*;
 
 #[derive(Clone)]
 struct Guard {
@@ -55,7 +21,7 @@ impl Guard {
         None
     }
 
-    fn do_next_move(&mut self, map: &mut Vec<Vec<char>>, direction: Direction) {
+    fn do_next_move(&mut self, map: &mut Vec<Vec<char>>, direction: Dir4) {
         if let Some((row, col)) = direction.next_position(self.position, map.len(), map[0].len()) {
             match map[row][col] {
                 '#' => {
@@ -102,7 +68,7 @@ impl Guard {
         }
     }
 
-    fn can_push_wide(&mut self, map: &Vec<Vec<char>>, direction: &Direction) -> bool {
+    fn can_push_wide(&mut self, map: &Vec<Vec<char>>, direction: &Dir4) -> bool {
         if *direction == Right || *direction == Left {
             let (mut ghost_i, mut ghost_j) = self.position;
             while let Some((i, j)) =
@@ -161,7 +127,7 @@ impl Guard {
         }
     }
 
-    fn push_wide(&mut self, map: &mut Vec<Vec<char>>, direction: &Direction) {
+    fn push_wide(&mut self, map: &mut Vec<Vec<char>>, direction: &Dir4) {
         if *direction == Right || *direction == Left {
             let (mut ghost_i, mut ghost_j) = self.position;
             let mut last_char = '@';
@@ -231,7 +197,7 @@ impl Guard {
         // _print_map(map);
     }
 
-    fn do_next_move_wide(&mut self, map: &mut Vec<Vec<char>>, direction: &Direction) {
+    fn do_next_move_wide(&mut self, map: &mut Vec<Vec<char>>, direction: &Dir4) {
         if let Some((row, col)) = direction.next_position(self.position, map.len(), map[0].len()) {
             match map[row][col] {
                 '#' => {}
@@ -300,15 +266,19 @@ fn calculate_gps_coordinates(map: &Vec<Vec<char>>) -> u32 {
         .sum()
 }
 
+/// The warehouse map and the list of moves.
+fn split_input(input: &str) -> (&str, &str) {
+    let mut sections = sections(input);
+    let map = sections.next().unwrap_or_default();
+    let moves = sections.next().unwrap_or_default();
+    (map, moves)
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    let mut map: Vec<Vec<char>> = input
-        .lines()
-        .filter(|line| line.contains('#'))
-        .map(|line| line.chars().collect())
-        .collect();
-    let directions: Vec<Direction> = input
+    let (map, moves) = split_input(input);
+    let mut map: Vec<Vec<char>> = map.lines().map(|line| line.chars().collect()).collect();
+    let directions: Vec<Dir4> = moves
         .lines()
-        .filter(|line| line.contains(['v', '<', '>', '^']))
         .flat_map(|line| line.chars())
         .map(|char| match char {
             'v' => Down,
@@ -332,9 +302,9 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let mut map: Vec<Vec<char>> = input
+    let (map, moves) = split_input(input);
+    let mut map: Vec<Vec<char>> = map
         .lines()
-        .filter(|line| line.contains('#'))
         .map(|line| {
             let mut new_line = vec![];
             for char in line.chars() {
@@ -361,9 +331,8 @@ pub fn part_two(input: &str) -> Option<u32> {
             new_line
         })
         .collect();
-    let directions: Vec<Direction> = input
+    let directions: Vec<Dir4> = moves
         .lines()
-        .filter(|line| line.contains(['v', '<', '>', '^']))
         .flat_map(|line| line.chars())
         .map(|char| match char {
             'v' => Down,
@@ -1,9 +1,62 @@
 advent_of_code::solution!(17);
-use std::collections::HashMap;
+use advent_of_code::parse::{ints, sections};
+use std::fmt::{Debug, Display};
+use std::num::ParseIntError;
+use std::ops::{BitXor, BitXorAssign};
+use std::str::FromStr;
 use Instruction::*;
 use Operand::*;
 
-type Register = u64;
+/// The type of the registers. The puzzle fits into `u64`, `u128` allows longer quines.
+trait Register:
+    Copy
+    + Eq
+    + Debug
+    + Display
+    + BitXor<Output = Self>
+    + BitXorAssign
+    + From<u8>
+    + FromStr<Err = ParseIntError>
+{
+    const ZERO: Self;
+
+    /// Shifts right, shifting out every bit if `shift` is at least the register width.
+    fn shr(self, shift: Self) -> Self;
+
+    /// The lowest 3 bits.
+    fn low_bits(self) -> u8;
+
+    /// Appends a 3 bit digit, [`None`] if the value would not fit anymore.
+    fn push_digit(self, digit: u8) -> Option<Self>;
+}
+
+macro_rules! impl_register {
+    ($($type:ty),*) => {
+        $(
+            impl Register for $type {
+                const ZERO: Self = 0;
+
+                fn shr(self, shift: Self) -> Self {
+                    u32::try_from(shift)
+                        .ok()
+                        .and_then(|shift| self.checked_shr(shift))
+                        .unwrap_or(0)
+                }
+
+                fn low_bits(self) -> u8 {
+                    (self & 7) as u8
+                }
+
+                fn push_digit(self, digit: u8) -> Option<Self> {
+                    (self.leading_zeros() >= 3).then(|| self << 3 | digit as $type)
+                }
+            }
+        )*
+    };
+}
+
+impl_register!(u64, u128);
+
 #[derive(Debug)]
 enum Operand {
     LITERAL(u8),
@@ -11,11 +64,11 @@ enum Operand {
 }
 
 impl Operand {
-    fn get_value(&self, computer: &Computer) -> u64 {
+    fn get_value<R: Register>(&self, computer: &Computer<R>) -> R {
         match *self {
-            LITERAL(value) => value as u64,
+            LITERAL(value) => value.into(),
             COMBO(value) => match value {
-                0 | 1 | 2 | 3 => value as u64,
+                0..=3 => value.into(),
                 4 => computer.register_a,
                 5 => computer.register_b,
                 6 => computer.register_c,
@@ -30,6 +83,19 @@ impl Operand {
     }
 }
 
+impl Display for Operand {
+    /// Literal operands and combo operands 0 to 3 are numbers, combo operands 4 to 6 registers.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            LITERAL(value) | COMBO(value @ 0..=3) => write!(f, "{value}"),
+            COMBO(4) => write!(f, "A"),
+            COMBO(5) => write!(f, "B"),
+            COMBO(6) => write!(f, "C"),
+            COMBO(value) => write!(f, "<invalid combo {value}>"),
+        }
+    }
+}
+
 #[derive(Debug)]
 enum Instruction {
     ADV(Operand), // OPCODE 0
@@ -42,78 +108,110 @@ enum Instruction {
     CDV(Operand), // OPCODE 7
 }
 
-struct Computer {
-    register_a: Register,
-    register_b: Register,
-    register_c: Register,
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ADV(operand) => write!(f, "adv {operand}"),
+            BXL(operand) => write!(f, "bxl {operand}"),
+            BST(operand) => write!(f, "bst {operand}"),
+            JNZ(operand) => write!(f, "jnz {operand}"),
+            BXC(()) => write!(f, "bxc"),
+            OUT(operand) => write!(f, "out {operand}"),
+            BDV(operand) => write!(f, "bdv {operand}"),
+            CDV(operand) => write!(f, "cdv {operand}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Computer<R: Register = u64> {
+    register_a: R,
+    register_b: R,
+    register_c: R,
     instruction_pointer: usize,
-    output: Vec<u64>,
+    output: Vec<u8>,
 }
 
-impl Computer {
+impl<R: Register> Computer<R> {
     fn execute_instruction(&mut self, instruction: &Instruction) {
         match instruction {
-            ADV(operand) => self.register_a /= 2_u64.pow(operand.get_value(&self) as u32),
+            ADV(operand) => self.register_a = self.register_a.shr(operand.get_value(self)),
             BXL(operand) => {
-                self.register_b ^= operand.get_value(&self);
+                self.register_b ^= operand.get_value(self);
             }
             BST(operand) => {
-                self.register_b = operand.get_value(&self) % 8;
+                self.register_b = operand.get_value(self).low_bits().into();
             }
-            JNZ(operand) => {
-                if self.register_a != 0 {
-                    self.instruction_pointer = operand.get_value(&self) as usize / 2;
+            JNZ(LITERAL(target)) => {
+                if self.register_a != R::ZERO {
+                    self.instruction_pointer = *target as usize / 2;
                 }
             }
+            JNZ(COMBO(_)) => unreachable!("jnz always has a literal operand"),
             BXC(()) => {
                 self.register_b ^= self.register_c;
             }
             OUT(operand) => {
-                self.output.push(operand.get_value(&self) % 8);
-            }
-            BDV(operand) => {
-                self.register_b = self.register_a / 2_u64.pow(operand.get_value(&self) as u32)
-            }
-            CDV(operand) => {
-                self.register_c = self.register_a / 2_u64.pow(operand.get_value(&self) as u32)
+                self.output.push(operand.get_value(self).low_bits());
             }
+            BDV(operand) => self.register_b = self.register_a.shr(operand.get_value(self)),
+            CDV(operand) => self.register_c = self.register_a.shr(operand.get_value(self)),
         }
     }
 
-    fn execute_program(&mut self, program: &Vec<Instruction>) {
-        while let Some(instruction) = program.get(self.instruction_pointer) {
-            self.execute_instruction(instruction);
-            match instruction {
-                JNZ(_) => {
-                    if self.register_a == 0 {
-                        break;
-                    } else {
-                        continue;
-                    }
-                }
-                _ => self.instruction_pointer += 1,
+    /// Executes the instruction at the instruction pointer, returns `false` if the program halted.
+    fn step(&mut self, program: &[Instruction]) -> bool {
+        let Some(instruction) = program.get(self.instruction_pointer) else {
+            return false;
+        };
+        self.execute_instruction(instruction);
+        match instruction {
+            JNZ(_) if self.register_a != R::ZERO => true,
+            JNZ(_) => false,
+            _ => {
+                self.instruction_pointer += 1;
+                true
             }
         }
     }
 
+    fn execute_program(&mut self, program: &[Instruction]) {
+        while self.step(program) {}
+    }
+
     fn get_output(&self) -> String {
         self.output
             .iter()
             .map(|int| format!("{}", int))
             .collect::<Vec<String>>()
-            .join(", ")
+            .join(",")
     }
 }
 
-fn parse_program(input: &str) -> Vec<Instruction> {
-    input
-        .lines()
-        .filter(|line| line.contains("Program:"))
-        .flat_map(|line| line.split(' '))
-        .skip(1)
-        .take(1)
-        .flat_map(|str| str.split(',').map(|number| number.parse::<u8>().unwrap()))
-        .collect::<Vec<u8>>()
+/// The computer and the codes of its program, [`None`] if a register does not fit into `R`.
+fn parse_input<R: Register>(input: &str) -> Option<(Computer<R>, Vec<u8>)> {
+    let mut sections = sections(input);
+    let registers: Vec<R> = ints(sections.next().unwrap_or_default())
+        .collect::<Result<_, _>>()
+        .ok()?;
+    let codes: Vec<u8> = ints(sections.next().unwrap_or_default())
+        .collect::<Result<_, _>>()
+        .ok()?;
+    let &[register_a, register_b, register_c] = registers.as_slice() else {
+        return None;
+    };
+    let computer = Computer {
+        register_a,
+        register_b,
+        register_c,
+        instruction_pointer: 0,
+        output: vec![],
+    };
+    Some((computer, codes))
+}
+
+fn parse_program(codes: &[u8]) -> Vec<Instruction> {
+    codes
         .chunks(2)
         .map(|chunk| {
             let (opcode, operand) = (chunk[0], chunk[1]);
@@ -132,111 +230,108 @@ fn parse_program(input: &str) -> Vec<Instruction> {
         .collect()
 }
 
-fn calculate_start_range(exact_locations: &HashMap<usize, Vec<usize>>) -> Option<usize> {
-    let j = exact_locations.len();
-    let mut range_start = 0;
-    for k in 0..j {
-        let mut possible_values = exact_locations.get(&k)?.clone();
-        possible_values.sort();
-        range_start += possible_values[0] * 8_usize.pow((exact_locations.len() - k) as u32);
-    }
-    Some(range_start)
+/// One instruction per line with its address, e.g. `0: adv 3`.
+fn _disassemble(program: &[Instruction]) -> String {
+    program
+        .iter()
+        .enumerate()
+        .map(|(i, instruction)| format!("{}: {instruction}", 2 * i))
+        .collect::<Vec<String>>()
+        .join("\n")
 }
 
-fn backtrack(
-    i: &mut usize,
-    exact_locations: &mut HashMap<usize, Vec<usize>>,
-    computer_output: &mut Vec<Vec<u64>>,
-) {
-    for key in (0..*i).rev() {
-        match exact_locations.get_mut(&key) {
-            Some(possible_ops) => match possible_ops.len() {
-                0 => unreachable!("This should not be empty"),
-                1 => {
-                    exact_locations.remove(&key);
-                    break;
-                }
-                _ => {
-                    possible_ops.sort();
-                    possible_ops.remove(0);
-                    computer_output.remove(*i);
-                    break;
-                }
-            },
-            None => {
-                *i -= 1;
-            }
+/// Runs the program and lists every executed instruction with the registers after it.
+fn _trace<R: Register>(computer: &mut Computer<R>, program: &[Instruction]) -> String {
+    let mut trace = vec![];
+    while let Some(instruction) = program.get(computer.instruction_pointer) {
+        let address = 2 * computer.instruction_pointer;
+        let running = computer.step(program);
+        trace.push(format!(
+            "{address:>2}: {:<6} A={} B={} C={}",
+            instruction.to_string(),
+            computer.register_a,
+            computer.register_b,
+            computer.register_c
+        ));
+        if !running {
+            break;
         }
     }
+    trace.join("\n")
 }
 
-fn get_quine(program: &Vec<Instruction>, quine: &Vec<u64>) -> u64 {
-    let mut computer_output: Vec<Vec<u64>> = vec![]; // debug information for me
-    let mut exact_locations: HashMap<usize, Vec<usize>> = HashMap::new();
-    let mut i: usize = 0;
-    while quine.len() > i {
-        let digit = quine[quine.len() - 1 - i];
-        computer_output.push(vec![]);
-        let mut pushed = false;
-        match calculate_start_range(&exact_locations) {
-            Some(range_start) => {
-                for register_a in range_start..range_start + 8 {
-                    let mut computer = Computer {
-                        register_a: register_a as u64,
-                        register_b: 0,
-                        register_c: 0,
-                        instruction_pointer: 0,
-                        output: vec![],
-                    };
-                    computer.execute_program(program);
-                    computer_output[i].push(computer.output[0]);
-                    let new_output = computer.output[0];
-                    if new_output == digit {
-                        let exact_location = register_a - range_start;
-                        let entry = exact_locations.entry(i).or_insert(vec![exact_location]);
-                        if !entry.contains(&exact_location) {
-                            entry.push(exact_location)
-                        }
-                        pushed = true;
-                    }
-                    if computer.output == vec![2, 4, 1, 1, 7, 5, 1, 4, 0, 3, 4, 5, 5, 5, 3, 0] {
-                        return register_a as u64;
-                    }
-                }
-                if !pushed {
-                    backtrack(&mut i, &mut exact_locations, &mut computer_output);
-                } else {
-                    i += 1;
-                }
-            }
-            None => {
-                backtrack(&mut i, &mut exact_locations, &mut computer_output);
-            }
+/// Finds the lowest value for register A that makes the program output itself.
+///
+/// The program has to be a single loop ending in `jnz 0` that shifts A right by 3 bits with one
+/// `adv 3` per iteration, and whose other registers only depend on A. Then the last output
+/// only depends on the highest 3 bits of A, the one before on the highest 6 bits, and so on,
+/// so A can be built 3 bits at a time from the end of the program.
+fn find_quine<R: Register>(
+    computer: &Computer<R>,
+    program: &[Instruction],
+    codes: &[u8],
+) -> Option<R> {
+    let ends_with_jump = matches!(program.last(), Some(JNZ(LITERAL(0))));
+    let shifts = program
+        .iter()
+        .filter(|instruction| matches!(instruction, ADV(COMBO(3))))
+        .count();
+    let jumps = program
+        .iter()
+        .filter(|instruction| matches!(instruction, JNZ(_)))
+        .count();
+    if !ends_with_jump || shifts != 1 || jumps != 1 {
+        return None;
+    }
+
+    let runs_to = |register_a: R| {
+        let mut computer = Computer {
+            register_a,
+            output: vec![],
+            instruction_pointer: 0,
+            ..computer.clone()
+        };
+        computer.execute_program(program);
+        computer.output
+    };
+
+    // depth-first with the smallest digit first, so the first complete match is the lowest.
+    fn search<R: Register>(
+        prefix: R,
+        remaining: usize,
+        wanted: &[u8],
+        runs_to: &dyn Fn(R) -> Vec<u8>,
+    ) -> Option<R> {
+        if remaining == 0 {
+            return (prefix != R::ZERO).then_some(prefix);
         }
+        (0..8)
+            .filter_map(|digit| prefix.push_digit(digit))
+            .filter(|&register_a| runs_to(register_a) == wanted[remaining - 1..])
+            .find_map(|register_a| search(register_a, remaining - 1, wanted, runs_to))
     }
-    0
+    search(R::ZERO, codes.len(), codes, &runs_to)
 }
 
 pub fn part_one(input: &str) -> Option<String> {
-    let mut computer = Computer {
-        register_a: 65804993,
-        register_b: 0,
-        register_c: 0,
-        instruction_pointer: 0,
-        output: vec![],
-    };
-    let program = parse_program(input);
+    let (mut computer, codes) = parse_input::<u64>(input)?;
+    let program = parse_program(&codes);
     computer.execute_program(&program);
     let output = computer.get_output();
     Some(output)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let program = parse_program(input);
-    Some(get_quine(
-        &program,
-        &vec![2, 4, 1, 1, 7, 5, 1, 4, 0, 3, 4, 5, 5, 5, 3, 0],
-    ))
+pub fn part_two(input: &str) -> Option<u128> {
+    if let Some((computer, codes)) = parse_input::<u64>(input) {
+        let program = parse_program(&codes);
+        if let Some(register_a) = find_quine(&computer, &program, &codes) {
+            return Some(register_a.into());
+        }
+    }
+    // the quine might need more than 64 bits, so might the initial registers.
+    let (computer, codes) = parse_input::<u128>(input)?;
+    let program = parse_program(&codes);
+    find_quine(&computer, &program, &codes)
 }
 
 #[cfg(test)]
@@ -250,11 +345,68 @@ mod tests {
         assert_eq!(result, Some(String::from("4,6,3,5,6,3,5,2,1,0")));
     }
 
+    #[test]
+    fn test_disassemble() {
+        let (_, codes) = parse_input::<u64>(&advent_of_code::template::read_file_part(
+            "examples", DAY, 2,
+        ))
+        .unwrap();
+        let program = parse_program(&codes);
+        assert_eq!(_disassemble(&program), "0: adv 3\n2: out A\n4: jnz 0");
+        assert_eq!(
+            _disassemble(&parse_program(&[2, 4, 1, 1, 7, 5, 4, 0, 6, 7])),
+            "0: bst A\n2: bxl 1\n4: cdv B\n6: bxc\n8: bdv <invalid combo 7>"
+        );
+    }
+
+    #[test]
+    fn test_trace() {
+        let (mut computer, codes) = parse_input::<u64>(&advent_of_code::template::read_file_part(
+            "examples", DAY, 1,
+        ))
+        .unwrap();
+        let program = parse_program(&codes);
+        let trace = _trace(&mut computer, &program);
+        let lines: Vec<&str> = trace.lines().collect();
+        assert_eq!(lines[0], " 0: adv 1  A=364 B=0 C=0");
+        assert_eq!(lines[1], " 2: out A  A=364 B=0 C=0");
+        assert_eq!(lines[2], " 4: jnz 0  A=364 B=0 C=0");
+        assert_eq!(lines.len(), 30);
+        assert_eq!(computer.output.len(), 10);
+    }
+
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file_part(
             "examples", DAY, 2,
         ));
         assert_eq!(result, Some(117440));
+
+        // adv 3, out A, nine times bxl 0, jnz 0: the quine needs 72 bits.
+        let codes = [
+            0, 3, 5, 4, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 3, 0,
+        ];
+        let program = parse_program(&codes);
+        let computer = Computer::<u64> {
+            register_a: 0,
+            register_b: 0,
+            register_c: 0,
+            instruction_pointer: 0,
+            output: vec![],
+        };
+        assert_eq!(find_quine(&computer, &program, &codes), None);
+
+        let quine = codes
+            .iter()
+            .enumerate()
+            .fold(0_u128, |a, (i, &code)| a | (code as u128) << (3 * (i + 1)));
+        let input = format!("Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: {codes:?}");
+        assert_eq!(part_two(&input), Some(quine));
+
+        // programs that do not shift A once per output are not supported.
+        let result = part_two(&advent_of_code::template::read_file_part(
+            "examples", DAY, 1,
+        ));
+        assert_eq!(result, None);
     }
 }
@@ -1,243 +1,90 @@
 advent_of_code::solution!(18);
-use std::cmp::{Ord, Ordering, Reverse};
-use std::collections::BinaryHeap;
-use Direction::*;
+use advent_of_code::dsu::DisjointSet;
+use advent_of_code::queue::{Queue, QueueKind};
+use advent_of_code::search::dijkstra_with;
+use advent_of_code::Grid;
 
 type Position = (usize, usize);
 
-#[derive(PartialEq, Eq, Clone, Debug)]
-struct Path {
-    path: Vec<Position>,
-    end_position: Position,
-}
-
-impl Path {
-    fn score(&self) -> u32 {
-        self.current_cost() + self.heuristic()
-    }
-
-    fn current_cost(&self) -> u32 {
-        self.path.len() as u32
-    }
-
-    fn heuristic(&self) -> u32 {
-        let current_position = self.path[self.path.len() - 1];
-        (current_position.0.abs_diff(self.end_position.0)
-            + current_position.1.abs_diff(self.end_position.1)) as u32
+/// The example uses a 7x7 memory space and its first 12 bytes, real inputs use 71x71 and 1024.
+fn dimensions(obstacles: &[Position]) -> (usize, usize) {
+    if obstacles.iter().all(|&(row, column)| row < 7 && column < 7) {
+        (7, 12)
+    } else {
+        (71, 1024)
     }
 }
 
-impl PartialOrd for Path {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+fn parse_obstacles(input: &str) -> Vec<Position> {
+    input
+        .lines()
+        .map(|line| line.split(',').collect::<Vec<&str>>())
+        .map(|coordinates| {
+            (
+                coordinates[1].trim().parse::<usize>().unwrap(),
+                coordinates[0].trim().parse::<usize>().unwrap(),
+            )
+        })
+        .collect()
 }
 
-impl Ord for Path {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.score().cmp(&other.score())
+fn shortest_path(size: usize, obstacles: &[Position]) -> Option<u32> {
+    let mut map = Grid::filled(size, size, false);
+    for &obstacle in obstacles {
+        map[obstacle] = true;
     }
-}
 
-#[derive(Clone, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
+    let end = (size - 1, size - 1);
+    dijkstra_with(
+        Queue::from_env_or(QueueKind::Bucket, true),
+        (0, 0),
+        |&position| {
+            map.neighbours4(position)
+                .filter(|next| !map[*next])
+                .map(|next| (next, 1))
+        },
+        |&position| position == end,
+    )
+    .cost()
 }
 
-impl Direction {
-    fn get(
-        &self,
-        map: &Vec<Vec<char>>,
-        coordinates: (usize, usize),
-    ) -> Option<(char, (usize, usize))> {
-        let (i, j) = coordinates;
-        match *self {
-            Up => {
-                let new_coordinates = (i.checked_sub(1)?, j);
-                let (new_i, new_j) = new_coordinates;
-                Some((map[new_i][new_j], new_coordinates))
-            }
-            Down => {
-                let new_coordinates = (i + 1, j);
-                let (new_i, new_j) = new_coordinates;
-                Some((map.get(new_i)?[new_j], new_coordinates))
-            }
-            Left => {
-                let new_coordinates = (i, j.checked_sub(1)?);
-                let (new_i, new_j) = new_coordinates;
-                Some((map[new_i][new_j], new_coordinates))
-            }
-            Right => {
-                let new_coordinates = (i, j + 1);
-                let (new_i, new_j) = new_coordinates;
-                Some((*map[new_i].get(new_j)?, new_coordinates))
-            }
-        }
-    }
-}
-
-#[derive(Clone)]
-struct Searcher {
-    map: Vec<Vec<char>>,
-    position: Position,
-    current_path: Vec<Position>,
-    finished: bool,
-    priority_queue: BinaryHeap<Reverse<Path>>,
+pub fn part_one(input: &str) -> Option<u32> {
+    let obstacles = parse_obstacles(input);
+    let (size, fallen) = dimensions(&obstacles);
+    shortest_path(size, &obstacles[..fallen])
 }
 
-const DIRECTIONS: [Direction; 4] = [Down, Right, Up, Left];
+pub fn part_two(input: &str) -> Option<usize> {
+    let obstacles = parse_obstacles(input);
+    let (size, _) = dimensions(&obstacles);
 
-impl Searcher {
-    fn init(map: &Vec<Vec<char>>) -> Self {
-        let mut priority_queue = BinaryHeap::with_capacity(map.len() * map[0].len() / 3);
-        let path = Path {
-            path: vec![(0, 0)],
-            end_position: (map.len(), map[0].len()),
-        };
-        priority_queue.push(Reverse(path));
-        Searcher {
-            map: map.clone(),
-            position: (0, 0),
-            current_path: vec![],
-            finished: false,
-            priority_queue,
-        }
+    // remove the bytes in reverse order until start and end are connected again.
+    let mut blocked = Grid::filled(size, size, false);
+    for &obstacle in &obstacles {
+        blocked[obstacle] = true;
     }
-
-    fn explore_next_path(&mut self) -> bool {
-        if let Some(next_path) = self.priority_queue.pop() {
-            self.current_path = next_path.0.path.clone();
-            self.position = next_path.0.path[next_path.0.path.len() - 1];
-            for direction in DIRECTIONS {
-                if let Some((symbol, position)) = direction.get(&self.map, self.position) {
-                    if symbol != '#' && !self.current_path.contains(&position) {
-                        let mut new_path = self.current_path.clone();
-                        new_path.push(position);
-                        let new_path = Path {
-                            path: new_path,
-                            end_position: (self.map.len(), self.map[0].len()),
-                        };
-
-                        if !self
-                            .priority_queue
-                            .iter()
-                            .any(|path| path.0.path.contains(&position))
-                        {
-                            self.priority_queue.push(Reverse(new_path));
-                        }
-                    }
-                    if position == (self.map.len() - 1, self.map[0].len() - 1) {
-                        self.finished = true;
-                    };
-                };
-            }
-            true
-        } else {
-            false
+    let mut sets = DisjointSet::new(blocked.len());
+    let connect = |sets: &mut DisjointSet, blocked: &Grid<bool>, position: Position| {
+        for next in blocked.neighbours4(position).filter(|next| !blocked[*next]) {
+            sets.union(blocked.index_of(position), blocked.index_of(next));
         }
+    };
+    for position in blocked.positions().filter(|position| !blocked[*position]) {
+        connect(&mut sets, &blocked, position);
     }
 
-    fn find_path_to_finish(&mut self) -> Option<Vec<Position>> {
-        while !self.finished {
-            if !self.explore_next_path() {
-                return None;
-            }
-        }
-        Some(self.current_path.clone())
+    let (start, end) = (0, blocked.len() - 1);
+    if sets.same(start, end) {
+        return Some(obstacles.len());
     }
-}
-
-fn _print_map(map: &Vec<Vec<char>>) {
-    for row in map {
-        for c in row {
-            print!("{c}");
+    for (i, &obstacle) in obstacles.iter().enumerate().rev() {
+        blocked[obstacle] = false;
+        connect(&mut sets, &blocked, obstacle);
+        if sets.same(start, end) {
+            return Some(i);
         }
-        println!("");
     }
-    println!("");
-}
-
-fn _print_map_with_path(map: &Vec<Vec<char>>, path: &Vec<Position>) {
-    for (i, row) in map.iter().enumerate() {
-        for (j, c) in row.iter().enumerate() {
-            if path.contains(&(i, j)) {
-                if *c == '.' {
-                    print!("O")
-                } else {
-                    print!("X")
-                }
-            } else {
-                print!("{c}");
-            }
-        }
-        println!("");
-    }
-    println!("");
-}
-
-fn get_free_map(dimensions: usize) -> Vec<Vec<char>> {
-    let mut map: Vec<Vec<char>> = vec![];
-    for _ in 0..dimensions {
-        map.push(vec!['.'].repeat(dimensions))
-    }
-    map
-}
-
-fn populate_map_with_obstacles(
-    map: &mut Vec<Vec<char>>,
-    obstacles: Vec<Position>,
-    number_of_obstacles: usize,
-) {
-    for position in obstacles[..number_of_obstacles].iter() {
-        map[position.0][position.1] = '#';
-    }
-}
-
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut map = get_free_map(71);
-    let obstacles: Vec<Position> = input
-        .lines()
-        .map(|line| line.split(',').collect::<Vec<&str>>())
-        .map(|coordinates| {
-            (
-                coordinates[1].trim().parse::<usize>().unwrap(),
-                coordinates[0].trim().parse::<usize>().unwrap(),
-            )
-        })
-        .collect();
-    populate_map_with_obstacles(&mut map, obstacles, 1024);
-    let mut searcher = Searcher::init(&map);
-    let path = searcher.find_path_to_finish().unwrap();
-    Some(path.len() as u32)
-}
-
-pub fn part_two(input: &str) -> Option<usize> {
-    let map_orig = get_free_map(71);
-    let obstacles: Vec<Position> = input
-        .lines()
-        .map(|line| line.split(',').collect::<Vec<&str>>())
-        .map(|coordinates| {
-            (
-                coordinates[1].trim().parse::<usize>().unwrap(),
-                coordinates[0].trim().parse::<usize>().unwrap(),
-            )
-        })
-        .collect();
-
-    let cutoff = (0..obstacles.len())
-        .collect::<Vec<usize>>()
-        .partition_point(|&i| {
-            let mut map = map_orig.clone();
-            populate_map_with_obstacles(&mut map, obstacles.clone(), i + 1);
-            let mut searcher = Searcher::init(&map);
-            searcher.find_path_to_finish().is_some()
-        });
-    // let obstacle = obstacles[cutoff];
-    // println!("The obstacle is {:?}", (obstacle.1, obstacle.0));
-    Some(cutoff)
+    None
 }
 
 #[cfg(test)]
@@ -1,69 +1,41 @@
+use advent_of_code::memo::Memo;
+use advent_of_code::parse::sections;
 use std::collections::{hash_map::Entry, HashMap};
 
 advent_of_code::solution!(19);
 
-fn is_possible(design: &Vec<char>, available_patterns: &HashMap<char, Vec<Vec<char>>>) -> bool {
-    if design.len() == 0 {
+fn is_possible(design: &[char], available_patterns: &HashMap<char, Vec<Vec<char>>>) -> bool {
+    if design.is_empty() {
         true
     } else {
-        let first_color = design[0];
         available_patterns
-            .get(&first_color)
-            .unwrap_or(&vec![])
+            .get(&design[0])
+            .map_or(&[][..], Vec::as_slice)
             .iter()
-            .filter(|&pattern| {
-                pattern
-                    .iter()
-                    .zip(design)
-                    .map(|pair| *pair.0 == *pair.1)
-                    .all(|can_continue| can_continue)
-            })
-            .any(|pattern| {
-                pattern.len() <= design.len()
-                    && is_possible(&design[pattern.len()..].to_vec(), available_patterns)
-            })
+            .filter(|pattern| design.starts_with(pattern))
+            .any(|pattern| is_possible(&design[pattern.len()..], available_patterns))
     }
 }
 
 fn count_possibilities(
-    design: &Vec<char>,
+    design: &[char],
     available_patterns: &HashMap<char, Vec<Vec<char>>>,
-    cache: &mut HashMap<Vec<char>, u64>,
+    memo: &mut Memo<Vec<char>, u64>,
 ) -> u64 {
-    if design.len() == 0 {
+    if design.is_empty() {
         1
     } else {
-        match cache.get(design) {
-            Some(cached_value) => *cached_value,
-            None => {
-                let first_color = design[0];
-                let result = available_patterns
-                    .get(&first_color)
-                    .unwrap_or(&vec![])
-                    .iter()
-                    .filter(|&pattern| {
-                        pattern
-                            .iter()
-                            .zip(design)
-                            .map(|pair| *pair.0 == *pair.1)
-                            .all(|can_continue| can_continue)
-                    })
-                    .map(|pattern| {
-                        if pattern.len() <= design.len() {
-                            count_possibilities(
-                                &design[pattern.len()..].to_vec(),
-                                available_patterns,
-                                cache,
-                            )
-                        } else {
-                            0
-                        }
-                    })
-                    .sum();
-                cache.insert(design.clone(), result);
-                result
-            }
-        }
+        memo.get_or_insert_with(design, |memo| {
+            available_patterns
+                .get(&design[0])
+                .map_or(&[][..], Vec::as_slice)
+                .iter()
+                .filter(|pattern| design.starts_with(pattern))
+                .map(|pattern| {
+                    count_possibilities(&design[pattern.len()..], available_patterns, memo)
+                })
+                .sum()
+        })
     }
 }
 
@@ -81,21 +53,20 @@ pub fn part_one(input: &str) -> Option<u64> {
 pub fn part_two(input: &str) -> Option<u64> {
     let available_patterns = parse_available_patterns(input);
     let wanted_designs = parse_wanted_designs(input);
-    let mut cache: HashMap<Vec<char>, u64> = HashMap::new();
+    let mut memo = Memo::new();
     Some(
         wanted_designs
             .iter()
-            .map(|design| count_possibilities(design, &available_patterns, &mut cache))
+            .map(|design| count_possibilities(design, &available_patterns, &mut memo))
             .sum(),
     )
 }
 
 fn parse_available_patterns(input: &str) -> HashMap<char, Vec<Vec<char>>> {
     let mut available_patterns: HashMap<char, Vec<Vec<char>>> = HashMap::new();
-    let patterns: Vec<&str> = input
-        .lines()
+    let patterns: Vec<&str> = sections(input)
         .take(1)
-        .flat_map(|line| line.split(',').map(|pattern| pattern.trim()))
+        .flat_map(|section| section.split(',').map(|pattern| pattern.trim()))
         .collect();
     for pattern in patterns {
         let pattern_as_chars = pattern.chars().collect::<Vec<char>>();
@@ -113,9 +84,9 @@ fn parse_available_patterns(input: &str) -> HashMap<char, Vec<Vec<char>>> {
 }
 
 fn parse_wanted_designs(input: &str) -> Vec<Vec<char>> {
-    input
-        .lines()
-        .skip(2)
+    sections(input)
+        .skip(1)
+        .flat_map(|section| section.lines())
         .map(|line| line.chars().collect())
         .collect()
 }
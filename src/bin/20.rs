@@ -1,171 +1,61 @@
 advent_of_code::solution!(20);
-use Direction::*;
+use advent_of_code::{Grid, Point};
 
 type Position = (usize, usize);
 
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
-}
-
-impl Direction {
-    fn next_position<T: Copy>(&self, map: &Vec<Vec<T>>, position: Position) -> (T, Position) {
-        match *self {
-            Up => {
-                let (i, j) = (position.0 - 1, position.1);
-                (map[i][j], (i, j))
-            }
-            Right => {
-                let (i, j) = (position.0, position.1 + 1);
-                (map[i][j], (i, j))
-            }
-            Down => {
-                let (i, j) = (position.0 + 1, position.1);
-                (map[i][j], (i, j))
-            }
-            Left => {
-                let (i, j) = (position.0, position.1 - 1);
-                (map[i][j], (i, j))
-            }
-        }
-    }
-
-    fn _jump_position<T: Copy>(
-        &self,
-        map: &Vec<Vec<T>>,
-        position: Position,
-    ) -> Option<(T, Position)> {
-        match *self {
-            Up => {
-                let (i, j) = (position.0.checked_sub(2)?, position.1);
-                Some((map.get(i)?[position.1], (i, j)))
-            }
-            Right => {
-                let (i, j) = (position.0, position.1 + 2);
-                Some((*map[i].get(j)?, (i, j)))
-            }
-            Down => {
-                let (i, j) = (position.0 + 2, position.1);
-                Some((map.get(i)?[j], (i, j)))
-            }
-            Left => {
-                let (i, j) = (position.0, position.1.checked_sub(2)?);
-                Some((*map[i].get(position.1)?, (i, j)))
-            }
-        }
-    }
-
-    fn can_turn_left(&self, map: &Vec<Vec<char>>, position: Position) -> bool {
-        let new_dir = self.turn_left();
-        let (symbol, _position) = new_dir.next_position(map, position);
-        symbol == '.'
-    }
-
-    fn turn_left(&self) -> Self {
-        match *self {
-            Up => Left,
-            Left => Down,
-            Down => Right,
-            Right => Up,
-        }
-    }
-
-    fn can_turn_right(&self, map: &Vec<Vec<char>>, position: Position) -> bool {
-        let new_dir = self.turn_right();
-        // new_dir.next_position(map, position).0 == '.'
-        let (symbol, _position) = new_dir.next_position(map, position);
-        symbol == '.'
-    }
-
-    fn turn_right(&self) -> Self {
-        match *self {
-            Up => Right,
-            Right => Down,
-            Down => Left,
-            Left => Up,
-        }
+/// The positions of the single track from `S` to `E`, so the index is the time to reach it.
+fn race_track(map: &Grid<char>) -> Option<Vec<Position>> {
+    let start = map.find(&'S')?;
+    let end = map.find(&'E')?;
+    let mut track = vec![start];
+    let mut previous = None;
+    let mut position = start;
+
+    while position != end {
+        let next = map
+            .neighbours4(position)
+            .find(|&next| map[next] != '#' && Some(next) != previous)?;
+        previous = Some(position);
+        position = next;
+        track.push(position);
     }
+    Some(track)
 }
 
-struct CheaterProgram {
-    char_map: Vec<Vec<char>>,
-    int_map: Vec<Vec<i32>>,
-    direction: Direction,
-    position: Position,
-    path_len: u32,
-}
-
-impl CheaterProgram {
-    fn init(map: &Vec<Vec<char>>) -> Self {
-        let vec = vec![-1].repeat(map.len());
-        let mut int_map = vec![vec.clone()];
-        for _ in 0..map[0].len() {
-            int_map.push(vec.clone());
-        }
-        CheaterProgram {
-            char_map: map.clone(),
-            int_map,
-            direction: Up,
-            position: (3, 1),
-            path_len: 0,
-        }
-    }
-
-    fn do_init_run(&mut self) {
-        self.int_map[self.position.0][self.position.1] = 0;
-        self.path_len += 1;
-        loop {
-            let (symbol, position) = self.direction.next_position(&self.char_map, self.position);
-            match symbol {
-                '.' => {
-                    self.int_map[position.0][position.1] = self.path_len as i32;
-                    self.path_len += 1;
-                    self.position = position
-                }
-                '#' | 'E' => {
-                    if self.direction.can_turn_left(&self.char_map, self.position) {
-                        self.direction = self.direction.turn_left();
-                    } else if self.direction.can_turn_right(&self.char_map, self.position) {
-                        self.direction = self.direction.turn_right();
-                    } else {
-                        break;
-                    }
-                }
-                _ => unreachable!("Found unknown char in the map"),
+/// Counts the cheats of at most `max_cheat` picoseconds that save at least `min_saving`.
+/// A cheat goes straight through walls from one track position to a later one, so it takes
+/// the Manhattan distance between them and saves the rest of their distance along the track.
+fn count_cheats(track: &[Position], max_cheat: usize, min_saving: usize) -> u32 {
+    let points: Vec<Point<usize>> = track
+        .iter()
+        .map(|&position| Point::<usize>::from_grid(position).unwrap())
+        .collect();
+
+    let mut cheats = 0;
+    for (i, from) in points.iter().enumerate() {
+        // skipping fewer than `min_saving` positions cannot save enough.
+        for (j, to) in points.iter().enumerate().skip(i + min_saving.max(1)) {
+            let cheat = from.manhattan(*to);
+            if cheat <= max_cheat && j - i - cheat >= min_saving {
+                cheats += 1;
             }
         }
     }
+    cheats
+}
 
-    fn _print_map(&self) {
-        for row in self.int_map.iter() {
-            for c in row {
-                if *c == -1 {
-                    print!("  .  ");
-                } else {
-                    print!(" {:03?} ", c);
-                }
-            }
-            println!("");
-        }
-        println!("");
-    }
+fn cheats(input: &str, max_cheat: usize, min_saving: usize) -> Option<u32> {
+    let map: Grid<char> = input.parse().ok()?;
+    let track = race_track(&map)?;
+    Some(count_cheats(&track, max_cheat, min_saving))
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let map: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
-    let mut cheater = CheaterProgram::init(&map);
-    cheater.do_init_run();
-    cheater._print_map();
-
-    // let (_path, score) = cheater.find_path_to_finish().unwrap();
-    // Some(score)
-    None
+    cheats(input, 2, 100)
 }
 
-pub fn part_two(_input: &str) -> Option<u32> {
-    None
+pub fn part_two(input: &str) -> Option<u32> {
+    cheats(input, 20, 100)
 }
 
 #[cfg(test)]
@@ -175,12 +65,24 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(0));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(0));
+    }
+
+    #[test]
+    fn test_cheats() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        assert_eq!(cheats(&input, 2, 1), Some(44));
+        assert_eq!(cheats(&input, 2, 20), Some(5));
+        assert_eq!(cheats(&input, 2, 64), Some(1));
+        assert_eq!(cheats(&input, 2, 65), Some(0));
+        assert_eq!(cheats(&input, 20, 50), Some(285));
+        assert_eq!(cheats(&input, 20, 74), Some(7));
+        assert_eq!(cheats(&input, 20, 76), Some(3));
     }
 }
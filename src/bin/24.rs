@@ -1,4 +1,6 @@
-use std::collections::HashMap;
+use advent_of_code::graph::{Graph, NodeId};
+use advent_of_code::parse::{key_values, sections_with_lines, ParseError};
+use advent_of_code::Interner;
 
 use Gate::*;
 use Signal::*;
@@ -14,7 +16,7 @@ enum Signal {
     NoValue,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 enum Gate {
     AND(Input, Output),
     OR(Input, Output),
@@ -22,106 +24,101 @@ enum Gate {
 }
 
 impl Gate {
-    fn calculate(&self, wires: &mut Vec<Signal>) {
-        if !self.need_input(wires) {
-            match *self {
-                AND((op1, op2), output) => {
-                    if wires[op1] == On && wires[op2] == On {
-                        wires[output] = On
-                    } else {
-                        wires[output] = Off
-                    }
-                }
-                OR((op1, op2), output) => {
-                    if wires[op1] == On || wires[op2] == On {
-                        wires[output] = On
-                    } else {
-                        wires[output] = Off
-                    }
-                }
-                XOR((op1, op2), output) => {
-                    if wires[op1] != wires[op2] {
-                        wires[output] = On
-                    } else {
-                        wires[output] = Off
-                    }
-                }
-            }
+    /// Applies the gate bitwise, so every bit of `a` and `b` can belong to another evaluation.
+    fn apply(&self, a: u64, b: u64) -> u64 {
+        match self {
+            AND(..) => a & b,
+            OR(..) => a | b,
+            XOR(..) => a ^ b,
+        }
+    }
+
+    fn inputs(&self) -> Input {
+        match *self {
+            AND(input, _) | OR(input, _) | XOR(input, _) => input,
         }
     }
 
-    fn is_done(&self, wires: &Vec<Signal>) -> bool {
+    fn output(&self) -> Output {
         match *self {
-            AND(_, output) => wires[output] != NoValue,
-            OR(_, output) => wires[output] != NoValue,
-            XOR(_, output) => wires[output] != NoValue,
+            AND(_, output) | OR(_, output) | XOR(_, output) => output,
         }
     }
 
-    fn need_input(&self, wires: &Vec<Signal>) -> bool {
+    fn with_output(&self, output: Output) -> Gate {
         match *self {
-            AND((op1, op2), _) => wires[op1] == NoValue || wires[op2] == NoValue,
-            OR((op1, op2), _) => wires[op1] == NoValue || wires[op2] == NoValue,
-            XOR((op1, op2), _) => wires[op1] == NoValue || wires[op2] == NoValue,
+            AND(input, _) => AND(input, output),
+            OR(input, _) => OR(input, output),
+            XOR(input, _) => XOR(input, output),
         }
     }
 }
 
-fn parse_input(input: &str) -> (Vec<Signal>, HashMap<String, usize>, Vec<Gate>) {
+fn parse_input(input: &str) -> Result<(Vec<Signal>, Interner, Vec<Gate>), ParseError> {
     let mut wires: Vec<Signal> = vec![];
-    let mut wires_indices: HashMap<String, usize> = HashMap::new();
-    input
-        .lines()
-        .filter(|line| line.contains(": "))
-        .map(|line| line.split(": ").collect::<Vec<&str>>())
-        .for_each(|wire| {
-            wires.push(if wire[1].contains("1") { On } else { Off });
-            wires_indices.insert(wire[0].to_owned(), wires.len() - 1);
-        });
-    let mut gates: Vec<Gate> = vec![];
-    for gate in input
-        .lines()
-        .filter(|line| line.contains("->"))
-        .map(|line| {
-            line.split("->")
-                .map(|side| side.trim())
-                .collect::<Vec<&str>>()
-        })
-        .collect::<Vec<Vec<&str>>>()
-    {
-        // insert rightside
-        if !wires_indices.contains_key(gate[1]) {
-            wires.push(NoValue);
-            wires_indices.insert(gate[1].to_owned(), wires.len() - 1);
+    let mut wire_names = Interner::new();
+    let mut wire = |name: &str, signal: Signal, wires: &mut Vec<Signal>| {
+        let index = wire_names.intern(name) as usize;
+        if index == wires.len() {
+            wires.push(signal);
         }
-        // insert leftside
-        let leftside = gate[0]
-            .split(" ")
-            .map(|part| part.trim())
-            .collect::<Vec<&str>>();
-        let (op1, op2) = (leftside[0], leftside[2]);
-        if !wires_indices.contains_key(op1) {
-            wires.push(NoValue);
-            wires_indices.insert(op1.to_owned(), wires.len() - 1);
-        }
-        if !wires_indices.contains_key(op2) {
-            wires.push(NoValue);
-            wires_indices.insert(op2.to_owned(), wires.len() - 1);
-        }
-        let input: Input = (wires_indices[op1], wires_indices[op2]);
-        let output: Output = wires_indices[gate[1]];
-        let gate = match leftside[1] {
+        index
+    };
+
+    // NOTE: line numbers of errors are relative to the whole input, not to the section.
+    let mut sections = sections_with_lines(input);
+    let (values_line, initial_values) = sections.next().unwrap_or((1, ""));
+    let (gates_line, connections) = sections.next().unwrap_or((1, ""));
+
+    let initial_values = key_values(initial_values, ":").map_err(|e| e.in_section(values_line))?;
+    for (i, (name, value)) in initial_values.into_iter().enumerate() {
+        let signal = match value {
+            "1" => On,
+            "0" => Off,
+            _ => {
+                return Err(ParseError::new(
+                    values_line + i,
+                    format!("expected 0 or 1 for {name}, got {value:?}"),
+                ))
+            }
+        };
+        wire(name, signal, &mut wires);
+    }
+    let mut gates: Vec<Gate> = vec![];
+    let connections = key_values(connections, "->").map_err(|e| e.in_section(gates_line))?;
+    for (i, (leftside, output)) in connections.into_iter().enumerate() {
+        let leftside = leftside.split_whitespace().collect::<Vec<&str>>();
+        let &[op1, gate_type, op2] = leftside.as_slice() else {
+            return Err(ParseError::new(
+                gates_line + i,
+                format!(
+                    "expected `<wire> <gate> <wire>`, got {:?}",
+                    leftside.join(" ")
+                ),
+            ));
+        };
+        let output: Output = wire(output, NoValue, &mut wires);
+        let input: Input = (
+            wire(op1, NoValue, &mut wires),
+            wire(op2, NoValue, &mut wires),
+        );
+        let gate = match gate_type {
             "AND" => AND(input, output),
             "OR" => OR(input, output),
             "XOR" => XOR(input, output),
-            _ => unreachable!("Wrong gate"),
+            _ => {
+                return Err(ParseError::new(
+                    gates_line + i,
+                    format!("unknown gate {gate_type:?}"),
+                ))
+            }
         };
         gates.push(gate);
     }
-    (wires, wires_indices, gates)
+    Ok((wires, wire_names, gates))
 }
 
-fn _print_gates(wires: &Vec<Signal>, gates: &Vec<Gate>) {
+fn _print_gates(wires: &[Signal], gates: &[Gate]) {
     for gate in gates {
         let (op1, op2, output, gate_type): (usize, usize, usize, &str);
         match gate {
@@ -136,55 +133,271 @@ fn _print_gates(wires: &Vec<Signal>, gates: &Vec<Gate>) {
     }
 }
 
-fn _print_wire_values(wires: &Vec<Signal>, wire_indices: &HashMap<String, usize>) {
-    let mut keys = wire_indices
-        .keys()
-        .map(|key| key.to_owned())
-        .collect::<Vec<String>>();
-    keys.sort();
-    for key in keys
+fn _print_wire_values(wires: &[Signal], wire_names: &Interner) {
+    let mut names = wire_names.iter().collect::<Vec<_>>();
+    names.sort_by_key(|(_, name)| *name);
+    for (index, name) in names
         .iter()
-        .filter(|key| !key.contains('x') && !key.contains('y'))
+        .filter(|(_, name)| !name.contains('x') && !name.contains('y'))
     {
-        println!("{key}: {:?}", wires[wire_indices[key]])
+        println!("{name}: {:?}", wires[*index as usize])
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (mut wires, wire_indices, gates) = parse_input(input);
-    let mut done = gates.iter().filter(|gate| gate.is_done(&wires)).count();
-    while !gates.iter().all(|gate| gate.is_done(&wires)) {
-        for gate in gates.iter() {
-            gate.calculate(&mut wires);
+/// The gates of a circuit in an order where every gate comes after the gates computing its
+/// inputs, so a single pass evaluates the whole circuit.
+#[derive(Debug)]
+struct Circuit {
+    plan: Vec<Gate>,
+    wire_count: usize,
+    x: Vec<usize>,
+    y: Vec<usize>,
+    z: Vec<usize>,
+}
+
+impl Circuit {
+    /// Orders the gates topologically, errors with the wires of a loop if there is one.
+    fn compile(gates: &[Gate], wire_names: &Interner) -> Result<Self, String> {
+        // node ids are wire indices.
+        let mut edges = vec![];
+        let mut producers = vec![None; wire_names.len()];
+        for gate in gates {
+            let (op1, op2) = gate.inputs();
+            let output = gate.output();
+            for input in [op1, op2] {
+                edges.push((input as NodeId, output as NodeId));
+            }
+            producers[output] = Some(*gate);
+        }
+
+        let graph = Graph::from_ids(true, wire_names.len(), edges);
+        let order = graph.topological_sort().map_err(|error| {
+            let cycle: Vec<&str> = error
+                .cycle
+                .iter()
+                .map(|&id| wire_names.resolve(id))
+                .collect();
+            format!("the gates form a loop: {}", cycle.join(" -> "))
+        })?;
+        let wires = |prefix: &str| {
+            wire_names
+                .numbered(prefix)
+                .into_iter()
+                .map(|(_, id)| id as usize)
+                .collect()
+        };
+
+        Ok(Circuit {
+            plan: order
+                .into_iter()
+                .filter_map(|wire| producers[wire as usize])
+                .collect(),
+            wire_count: wire_names.len(),
+            x: wires("x"),
+            y: wires("y"),
+            z: wires("z"),
+        })
+    }
+
+    /// Evaluates the plan on `lanes`, which holds the value of every wire for 64 evaluations.
+    fn run(&self, lanes: &mut [u64]) {
+        for gate in &self.plan {
+            let (op1, op2) = gate.inputs();
+            lanes[gate.output()] = gate.apply(lanes[op1], lanes[op2]);
+        }
+    }
+
+    /// The number on the `z` wires in evaluation `lane`.
+    fn read_z(&self, lanes: &[u64], lane: usize) -> u64 {
+        self.z.iter().enumerate().fold(0, |sum, (bit, &wire)| {
+            sum | (lanes[wire] >> lane & 1) << bit
+        })
+    }
+
+    /// Evaluates the circuit on the initial values of the puzzle input.
+    /// Returns [`None`] if a `z` wire depends on a wire without an initial value or gate.
+    fn evaluate_signals(&self, signals: &[Signal]) -> Option<u64> {
+        let mut driven: Vec<bool> = signals.iter().map(|signal| *signal != NoValue).collect();
+        for gate in &self.plan {
+            let (op1, op2) = gate.inputs();
+            driven[gate.output()] = driven[op1] && driven[op2];
+        }
+        if !self.z.iter().all(|&wire| driven[wire]) {
+            return None;
         }
-        let newly_done = gates.iter().filter(|gate| gate.is_done(&wires)).count() - done;
 
-        if newly_done == 0 {
-            break;
-        } else {
-            done += newly_done
+        let mut lanes: Vec<u64> = signals
+            .iter()
+            .map(|signal| (*signal == On) as u64)
+            .collect();
+        self.run(&mut lanes);
+        Some(self.read_z(&lanes, 0))
+    }
+
+    /// Returns `z` for every `(x, y)` pair, evaluating 64 pairs per pass.
+    fn evaluate_many(&self, cases: &[(u64, u64)]) -> Vec<u64> {
+        let mut results = Vec::with_capacity(cases.len());
+        for chunk in cases.chunks(64) {
+            let mut lanes = vec![0; self.wire_count];
+            for (lane, &(x, y)) in chunk.iter().enumerate() {
+                for (wires, value) in [(&self.x, x), (&self.y, y)] {
+                    for (bit, &wire) in wires.iter().enumerate() {
+                        lanes[wire] |= (value >> bit & 1) << lane;
+                    }
+                }
+            }
+            self.run(&mut lanes);
+            results.extend((0..chunk.len()).map(|lane| self.read_z(&lanes, lane)));
         }
+        results
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Checks every gate against the shape of a ripple-carry adder, which computes bit `i` as
+/// `zi = (xi XOR yi) XOR carry` and the next carry as `(xi AND yi) OR ((xi XOR yi) AND carry)`.
+/// Bit 0 has no incoming carry and the last carry is the highest `z` wire.
+/// Returns the outputs of all gates that do not fit, sorted by name.
+fn misplaced_outputs(gates: &[Gate], wire_names: &Interner) -> Vec<Output> {
+    let name = |wire: usize| wire_names.resolve(wire as u32);
+    let is_input = |wire: usize| name(wire).starts_with(['x', 'y']);
+    let is_z = |wire: usize| name(wire).starts_with('z');
+    let last_z = wire_names.numbered("z").last().map(|&(_, id)| id as usize);
+
+    let mut consumers: Vec<Vec<&Gate>> = vec![vec![]; wire_names.len()];
+    for gate in gates {
+        let (op1, op2) = gate.inputs();
+        consumers[op1].push(gate);
+        consumers[op2].push(gate);
     }
+    let feeds =
+        |wire: usize, is_kind: fn(&Gate) -> bool| consumers[wire].iter().any(|g| is_kind(g));
 
-    let mut sum: u64 = 0;
-    for i in 0..64 {
-        let wire_name = format!("z{:02}", i);
-        if wire_indices.contains_key(&wire_name) {
-            let result = wires[wire_indices[&wire_name]].clone();
-            if result == On {
-                sum += 2_u64.pow(i)
+    let mut misplaced: Vec<Output> = gates
+        .iter()
+        .filter(|gate| {
+            let (op1, _) = gate.inputs();
+            let output = gate.output();
+            let first_bit = matches!(name(op1), "x00" | "y00");
+            if Some(output) == last_z {
+                return !matches!(gate, OR(..));
+            }
+            match gate {
+                _ if is_z(output) && !matches!(gate, XOR(..)) => true,
+                XOR(..) if is_input(op1) && first_bit => name(output) != "z00",
+                XOR(..) if is_input(op1) => !feeds(output, |g| matches!(g, XOR(..))),
+                XOR(..) => !is_z(output),
+                AND(..) if first_bit => !feeds(output, |g| matches!(g, XOR(..))),
+                AND(..) => {
+                    !feeds(output, |g| matches!(g, OR(..)))
+                        || feeds(output, |g| !matches!(g, OR(..)))
+                }
+                OR(..) => !feeds(output, |g| matches!(g, XOR(..))),
             }
-        } else {
-            break;
+        })
+        .map(|gate| gate.output())
+        .collect();
+    misplaced.sort_by_key(|&wire| name(wire));
+    misplaced
+}
+
+/// All ways to split `wires` into pairs.
+fn pairings(wires: &[Output]) -> Vec<Vec<(Output, Output)>> {
+    let Some((&first, rest)) = wires.split_first() else {
+        return vec![vec![]];
+    };
+    let mut pairings = vec![];
+    for (i, &partner) in rest.iter().enumerate() {
+        let others: Vec<Output> = rest[..i].iter().chain(&rest[i + 1..]).copied().collect();
+        for mut pairing in self::pairings(&others) {
+            pairing.push((first, partner));
+            pairings.push(pairing);
         }
     }
-    _print_wire_values(&wires, &wire_indices);
+    pairings
+}
+
+fn swap_outputs(gates: &[Gate], swaps: &[(Output, Output)]) -> Vec<Gate> {
+    gates
+        .iter()
+        .map(|gate| {
+            let output = gate.output();
+            let swapped = swaps.iter().find_map(|&(a, b)| match output {
+                _ if output == a => Some(b),
+                _ if output == b => Some(a),
+                _ => None,
+            });
+            gate.with_output(swapped.unwrap_or(output))
+        })
+        .collect()
+}
+
+/// Tries the circuit on every single bit and on pseudo-random numbers.
+fn adds_correctly(gates: &[Gate], wire_names: &Interner) -> bool {
+    let Ok(circuit) = Circuit::compile(gates, wire_names) else {
+        return false;
+    };
+    let bits = circuit.x.len();
+    if bits == 0 || bits >= 64 || circuit.y.len() != bits || circuit.z.len() != bits + 1 {
+        return false;
+    }
+    let mask = (1 << bits) - 1;
+
+    let mut cases = vec![(0, 0), (mask, 1), (mask, mask)];
+    for bit in 0..bits {
+        cases.extend([(1 << bit, 0), (0, 1 << bit), (1 << bit, 1 << bit)]);
+    }
+    // xorshift, fixed seed so failures are reproducible.
+    let mut state: u64 = 0x2024_1224;
+    let mut random = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state & mask
+    };
+    for _ in 0..64 {
+        cases.push((random(), random()));
+    }
+
+    circuit
+        .evaluate_many(&cases)
+        .into_iter()
+        .zip(&cases)
+        .all(|(z, (x, y))| z == x + y)
+}
+
+/// Finds how the `suspects` have to be swapped back so the circuit adds correctly.
+fn repair(
+    gates: &[Gate],
+    wire_names: &Interner,
+    suspects: &[Output],
+) -> Option<Vec<(Output, Output)>> {
+    if !suspects.len().is_multiple_of(2) || suspects.len() > 10 {
+        return None;
+    }
+    pairings(suspects)
+        .into_iter()
+        .find(|swaps| adds_correctly(&swap_outputs(gates, swaps), wire_names))
+}
 
-    Some(sum)
+pub fn part_one(input: &str) -> Option<u64> {
+    let (wires, wire_names, gates) = parse_input(input).ok()?;
+    let circuit = Circuit::compile(&gates, &wire_names).ok()?;
+    circuit.evaluate_signals(&wires)
 }
 
-pub fn part_two(_input: &str) -> Option<u32> {
-    None
+pub fn part_two(input: &str) -> Option<String> {
+    let (_, wire_names, gates) = parse_input(input).ok()?;
+    let suspects = misplaced_outputs(&gates, &wire_names);
+    repair(&gates, &wire_names, &suspects)?;
+    Some(
+        suspects
+            .iter()
+            .map(|&wire| wire_names.resolve(wire as u32))
+            .collect::<Vec<&str>>()
+            .join(","),
+    )
 }
 
 #[cfg(test)]
@@ -209,9 +422,80 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_one(&advent_of_code::template::read_file_part(
+        let result = part_two(&advent_of_code::template::read_file_part(
             "examples", DAY, 2,
         ));
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn test_circuit_rejects_loops() {
+        let input = "x00: 1\ny00: 0\n\nx00 AND abc -> def\ny00 OR def -> abc\nabc XOR def -> z00";
+        let (_, wire_names, gates) = parse_input(input).unwrap();
+        let error = Circuit::compile(&gates, &wire_names).unwrap_err();
+        assert_eq!(error, "the gates form a loop: abc -> def");
+        assert_eq!(part_one(input), None);
+    }
+
+    #[test]
+    fn test_parse_errors_point_at_input_lines() {
+        let error = parse_input("x00: 1\ny00: 0\n\nx00 NAND y00 -> z00").unwrap_err();
+        assert_eq!(error.to_string(), "line 4: unknown gate \"NAND\"");
+        let error = parse_input("x00: 1\n\nx00 AND y00 -> z00\nx00 y00 z00").unwrap_err();
+        assert_eq!(error.line, 4);
+        let error = parse_input("x00: 1\ny00: 2\n\nx00 AND y00 -> z00").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2: expected 0 or 1 for y00, got \"2\""
+        );
+    }
+
+    #[test]
+    fn test_part_one_undriven_wire() {
+        assert_eq!(part_one("x00: 1\n\nx00 AND abc -> z00"), None);
+        assert_eq!(part_one("x00: 1\nabc: 0\n\nx00 OR abc -> z00"), Some(1));
+    }
+
+    /// A ripple-carry adder for `bits` bits with the outputs of `swaps` exchanged.
+    fn broken_adder(bits: usize, swaps: &[(&str, &str)]) -> String {
+        let mut lines = vec![];
+        for bit in 0..bits {
+            lines.push(format!("x{bit:02}: 0\ny{bit:02}: 0"));
+        }
+        lines.push(String::new());
+        lines.push("x00 XOR y00 -> z00\nx00 AND y00 -> c00".to_string());
+        for i in 1..bits {
+            let carry = if i + 1 == bits {
+                format!("z{bits:02}")
+            } else {
+                format!("c{i:02}")
+            };
+            lines.push(format!(
+                "x{i:02} XOR y{i:02} -> s{i:02}\nx{i:02} AND y{i:02} -> a{i:02}\n\
+                 s{i:02} XOR c{p:02} -> z{i:02}\ns{i:02} AND c{p:02} -> m{i:02}\n\
+                 a{i:02} OR m{i:02} -> {carry}",
+                p = i - 1
+            ));
+        }
+        let mut input = lines.join("\n");
+        for (a, b) in swaps {
+            input = input
+                .replace(&format!("-> {a}"), "-> TMP")
+                .replace(&format!("-> {b}"), &format!("-> {a}"))
+                .replace("-> TMP", &format!("-> {b}"));
+        }
+        input
+    }
+
+    #[test]
+    fn test_part_two_finds_swapped_wires() {
+        let input = broken_adder(12, &[]);
+        assert_eq!(part_two(&input), Some(String::new()));
+
+        let input = broken_adder(12, &[("s03", "a03"), ("z07", "m07"), ("c05", "z09")]);
+        assert_eq!(
+            part_two(&input),
+            Some("a03,c05,m07,s03,z07,z09".to_string())
+        );
+    }
 }
@@ -1,4 +1,5 @@
 advent_of_code::solution!(25);
+use advent_of_code::parse::sections;
 
 type Lock = [usize; 5];
 type Key = [usize; 5];
@@ -30,7 +31,7 @@ fn parse_lock(input: &str) -> Lock {
 fn parse(input: &str) -> (Vec<Lock>, Vec<Key>) {
     let mut locks: Vec<Lock> = vec![];
     let mut keys: Vec<Key> = vec![];
-    input.split("\n\n").for_each(|input| {
+    sections(input).for_each(|input| {
         if input.chars().next() == Some('#') {
             locks.push(parse_lock(input))
         } else {
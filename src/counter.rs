@@ -0,0 +1,140 @@
+/// A multiset that counts how often each value occurs.
+///
+/// Puzzles where every item of a population evolves independently by the same rules only need
+/// to track the distinct values and their counts, so a step costs time proportional to the
+/// number of distinct values instead of the population size.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Debug, Clone)]
+pub struct Counter<K> {
+    counts: HashMap<K, u64>,
+}
+
+impl<K> Default for Counter<K> {
+    fn default() -> Self {
+        Counter {
+            counts: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq> Counter<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `n` occurrences of `key`.
+    pub fn add(&mut self, key: K, n: u64) {
+        if n > 0 {
+            *self.counts.entry(key).or_insert(0) += n;
+        }
+    }
+
+    /// How often `key` occurs, zero if it does not.
+    pub fn count(&self, key: &K) -> u64 {
+        self.counts.get(key).copied().unwrap_or(0)
+    }
+
+    /// The size of the whole population.
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    /// The number of distinct values.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// All distinct values with their counts, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, u64)> {
+        self.counts.iter().map(|(key, &count)| (key, count))
+    }
+
+    /// Replaces every value by the values `rule` turns it into, keeping the counts.
+    /// A value that `rule` turns into nothing dies out.
+    pub fn evolve<I>(&mut self, mut rule: impl FnMut(&K) -> I)
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let mut next = Counter::new();
+        for (key, count) in self.counts.drain() {
+            for child in rule(&key) {
+                next.add(child, count);
+            }
+        }
+        *self = next;
+    }
+
+    /// Evolves the population `generations` times.
+    pub fn evolve_n<I>(&mut self, generations: usize, mut rule: impl FnMut(&K) -> I)
+    where
+        I: IntoIterator<Item = K>,
+    {
+        for _ in 0..generations {
+            self.evolve(&mut rule);
+        }
+    }
+}
+
+impl<K: Hash + Eq> FromIterator<K> for Counter<K> {
+    fn from_iter<T: IntoIterator<Item = K>>(iter: T) -> Self {
+        let mut counter = Counter::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<K: Hash + Eq> Extend<K> for Counter<K> {
+    fn extend<T: IntoIterator<Item = K>>(&mut self, iter: T) {
+        for key in iter {
+            self.add(key, 1);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Counter;
+
+    #[test]
+    fn counts_values() {
+        let mut counter: Counter<char> = "abracadabra".chars().collect();
+        assert_eq!(counter.count(&'a'), 5);
+        assert_eq!(counter.count(&'z'), 0);
+        assert_eq!(counter.len(), 5);
+        assert_eq!(counter.total(), 11);
+
+        counter.add('z', 3);
+        counter.add('y', 0);
+        assert_eq!(counter.count(&'z'), 3);
+        assert_eq!(counter.len(), 6);
+    }
+
+    #[test]
+    fn evolves_populations() {
+        // lanternfish: a timer of 0 resets to 6 and spawns a new fish with a timer of 8.
+        let rule = |&timer: &u8| match timer {
+            0 => vec![6, 8],
+            timer => vec![timer - 1],
+        };
+        let mut fish: Counter<u8> = [3, 4, 3, 1, 2].into_iter().collect();
+
+        fish.evolve_n(18, rule);
+        assert_eq!(fish.total(), 26);
+        fish.evolve_n(80 - 18, rule);
+        assert_eq!(fish.total(), 5934);
+        fish.evolve_n(256 - 80, rule);
+        assert_eq!(fish.total(), 26_984_457_539);
+        assert!(fish.len() <= 9);
+
+        fish.evolve(|_| None);
+        assert!(fish.is_empty());
+    }
+}
@@ -0,0 +1,131 @@
+/// Cycle detection for deterministic simulations.
+///
+/// A simulation is a start state and a `step` function. Its states eventually repeat, so the
+/// sequence consists of `mu` states before the cycle followed by a cycle of `lambda` states.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Finds `(mu, lambda)` by remembering every state.
+/// Uses memory proportional to `mu + lambda`, but calls `step` only `mu + lambda` times.
+pub fn find_cycle<S: Clone + Hash + Eq>(start: S, mut step: impl FnMut(&S) -> S) -> (usize, usize) {
+    let Some(cycle) = try_find_cycle(start, |state| Some(step(state))) else {
+        unreachable!("the simulation never ends, so it has to repeat");
+    };
+    cycle
+}
+
+/// Like [`find_cycle`] for simulations that may end, i.e. `step` returns [`None`].
+/// Returns [`None`] if the simulation ended without repeating a state.
+pub fn try_find_cycle<S: Clone + Hash + Eq>(
+    start: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> Option<(usize, usize)> {
+    let mut seen = HashMap::new();
+    let mut state = start;
+
+    loop {
+        if let Some(&mu) = seen.get(&state) {
+            return Some((mu, seen.len() - mu));
+        }
+        let next = step(&state)?;
+        seen.insert(state, seen.len());
+        state = next;
+    }
+}
+
+/// Finds `(mu, lambda)` with Brent's algorithm.
+/// Keeps only two states in memory, but calls `step` roughly three times as often as
+/// [`find_cycle`], and does not need `Hash`.
+pub fn find_cycle_brent<S: Clone + Eq>(start: S, mut step: impl FnMut(&S) -> S) -> (usize, usize) {
+    // search successive powers of two for the cycle length.
+    let mut power = 1;
+    let mut lambda = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+    while tortoise != hare {
+        if power == lambda {
+            tortoise = hare.clone();
+            power *= 2;
+            lambda = 0;
+        }
+        hare = step(&hare);
+        lambda += 1;
+    }
+
+    // with the hare `lambda` steps ahead, both meet at the start of the cycle.
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..lambda {
+        hare = step(&hare);
+    }
+    let mut mu = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+
+    (mu, lambda)
+}
+
+/// The state after `n` steps, skipping whole cycles once a state repeats.
+pub fn state_at<S: Clone + Hash + Eq>(start: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    let mut seen = HashMap::new();
+    let mut history = vec![];
+    let mut state = start;
+
+    for i in 0..n {
+        if let Some(&mu) = seen.get(&state) {
+            let lambda = i - mu;
+            return history.swap_remove(mu + (n - mu) % lambda);
+        }
+        seen.insert(state.clone(), i);
+        history.push(state.clone());
+        state = step(&state);
+    }
+    state
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_cycle, find_cycle_brent, state_at, try_find_cycle};
+
+    /// 3 states before entering a cycle of 5 states: 0, 1, 2, 3, 4, 5, 6, 7, 3, ...
+    fn step(state: &u32) -> u32 {
+        if *state == 7 {
+            3
+        } else {
+            state + 1
+        }
+    }
+
+    #[test]
+    fn finds_cycles() {
+        assert_eq!(find_cycle(0, step), (3, 5));
+        assert_eq!(find_cycle_brent(0, step), (3, 5));
+        assert_eq!(find_cycle(3, step), (0, 5));
+        assert_eq!(find_cycle_brent(3, step), (0, 5));
+        assert_eq!(find_cycle(0, |_| 0), (0, 1));
+        assert_eq!(find_cycle_brent(0, |_| 0), (0, 1));
+    }
+
+    #[test]
+    fn handles_ending_simulations() {
+        assert_eq!(try_find_cycle(0, |s| (*s < 10).then(|| s + 1)), None);
+        assert_eq!(try_find_cycle(0, |s| Some(step(s))), Some((3, 5)));
+    }
+
+    #[test]
+    fn skips_cycles() {
+        for n in 0..30 {
+            let mut state = 0;
+            for _ in 0..n {
+                state = step(&state);
+            }
+            assert_eq!(state_at(0, step, n), state, "step {n}");
+        }
+        assert_eq!(state_at(0, step, 1_000_000_004), 4);
+    }
+}
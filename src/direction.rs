@@ -0,0 +1,254 @@
+/// Directions on a grid.
+use crate::point::Point;
+
+/// One of the four orthogonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    /// All directions, clockwise starting up.
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        match self {
+            Dir4::Up => Dir4::Right,
+            Dir4::Right => Dir4::Down,
+            Dir4::Down => Dir4::Left,
+            Dir4::Left => Dir4::Up,
+        }
+    }
+
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        match self {
+            Dir4::Up => Dir4::Left,
+            Dir4::Right => Dir4::Up,
+            Dir4::Down => Dir4::Right,
+            Dir4::Left => Dir4::Down,
+        }
+    }
+
+    #[must_use]
+    pub fn turn_around(self) -> Self {
+        match self {
+            Dir4::Up => Dir4::Down,
+            Dir4::Right => Dir4::Left,
+            Dir4::Down => Dir4::Up,
+            Dir4::Left => Dir4::Right,
+        }
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Dir4::Left | Dir4::Right)
+    }
+
+    pub fn is_vertical(self) -> bool {
+        !self.is_horizontal()
+    }
+
+    /// The `(row, column)` offset of a single step, see [`crate::Grid::offset`].
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Dir4::Up => (-1, 0),
+            Dir4::Right => (0, 1),
+            Dir4::Down => (1, 0),
+            Dir4::Left => (0, -1),
+        }
+    }
+
+    /// The vector of a single step, with `y` growing downwards.
+    pub fn vector(self) -> Point<i64> {
+        let (dy, dx) = self.offset();
+        Point::new(dx as i64, dy as i64)
+    }
+
+    /// Steps from a `(row, column)` position, returns [`None`] when leaving `max_rows` x `max_columns`.
+    pub fn next_position(
+        self,
+        position: (usize, usize),
+        max_rows: usize,
+        max_columns: usize,
+    ) -> Option<(usize, usize)> {
+        let (row, col) = position;
+        let next = match self {
+            Dir4::Up => (row.checked_sub(1)?, col),
+            Dir4::Right => (row, col + 1),
+            Dir4::Down => (row + 1, col),
+            Dir4::Left => (row, col.checked_sub(1)?),
+        };
+        (next.0 < max_rows && next.1 < max_columns).then_some(next)
+    }
+
+    /// Parses arrows (`^>v<`) and letters (`URDL`, `NESW`).
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' | 'U' | 'N' => Some(Dir4::Up),
+            '>' | 'R' | 'E' => Some(Dir4::Right),
+            'v' | 'V' | 'D' | 'S' => Some(Dir4::Down),
+            '<' | 'L' | 'W' => Some(Dir4::Left),
+            _ => None,
+        }
+    }
+}
+
+impl From<Dir4> for (isize, isize) {
+    fn from(value: Dir4) -> Self {
+        value.offset()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the four orthogonal or four diagonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    /// All directions, clockwise starting up.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::Up,
+        Dir8::UpRight,
+        Dir8::Right,
+        Dir8::DownRight,
+        Dir8::Down,
+        Dir8::DownLeft,
+        Dir8::Left,
+        Dir8::UpLeft,
+    ];
+
+    /// All diagonal directions, clockwise starting up right.
+    pub const DIAGONALS: [Dir8; 4] = [Dir8::UpRight, Dir8::DownRight, Dir8::DownLeft, Dir8::UpLeft];
+
+    fn rotate(self, steps: usize) -> Self {
+        let index = Self::ALL.iter().position(|d| *d == self).unwrap_or(0);
+        Self::ALL[(index + steps) % 8]
+    }
+
+    /// Turns clockwise by 45 degrees.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        self.rotate(1)
+    }
+
+    /// Turns counter-clockwise by 45 degrees.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        self.rotate(7)
+    }
+
+    #[must_use]
+    pub fn turn_around(self) -> Self {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        Self::DIAGONALS.contains(&self)
+    }
+
+    /// The `(row, column)` offset of a single step, see [`crate::Grid::offset`].
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Dir8::Up => (-1, 0),
+            Dir8::UpRight => (-1, 1),
+            Dir8::Right => (0, 1),
+            Dir8::DownRight => (1, 1),
+            Dir8::Down => (1, 0),
+            Dir8::DownLeft => (1, -1),
+            Dir8::Left => (0, -1),
+            Dir8::UpLeft => (-1, -1),
+        }
+    }
+
+    /// The vector of a single step, with `y` growing downwards.
+    pub fn vector(self) -> Point<i64> {
+        let (dy, dx) = self.offset();
+        Point::new(dx as i64, dy as i64)
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(value: Dir4) -> Self {
+        match value {
+            Dir4::Up => Dir8::Up,
+            Dir4::Right => Dir8::Right,
+            Dir4::Down => Dir8::Down,
+            Dir4::Left => Dir8::Left,
+        }
+    }
+}
+
+impl From<Dir8> for (isize, isize) {
+    fn from(value: Dir8) -> Self {
+        value.offset()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Dir4, Dir8};
+    use crate::point::Vec2;
+
+    #[test]
+    fn turns_dir4() {
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+        assert_eq!(Dir4::Left.turn_around(), Dir4::Right);
+        for dir in Dir4::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.turn_around());
+        }
+    }
+
+    #[test]
+    fn turns_dir8() {
+        assert_eq!(Dir8::Up.turn_right(), Dir8::UpRight);
+        assert_eq!(Dir8::Up.turn_left(), Dir8::UpLeft);
+        assert_eq!(Dir8::DownLeft.turn_around(), Dir8::UpRight);
+        assert!(Dir8::UpLeft.is_diagonal());
+        assert!(!Dir8::from(Dir4::Down).is_diagonal());
+    }
+
+    #[test]
+    fn converts_to_offsets() {
+        assert_eq!(Dir4::Up.offset(), (-1, 0));
+        assert_eq!(Dir4::Right.vector(), Vec2::new(1, 0));
+        assert_eq!(Dir8::DownLeft.vector(), Vec2::new(-1, 1));
+        assert_eq!(
+            Dir4::ALL.map(|d| Dir8::from(d).offset()),
+            Dir4::ALL.map(Dir4::offset)
+        );
+    }
+
+    #[test]
+    fn steps_within_bounds() {
+        assert_eq!(Dir4::Up.next_position((0, 1), 2, 2), None);
+        assert_eq!(Dir4::Left.next_position((0, 1), 2, 2), Some((0, 0)));
+        assert_eq!(Dir4::Right.next_position((0, 1), 2, 2), None);
+        assert_eq!(Dir4::Down.next_position((0, 1), 2, 2), Some((1, 1)));
+    }
+
+    #[test]
+    fn parses_chars() {
+        assert_eq!(Dir4::from_char('^'), Some(Dir4::Up));
+        assert_eq!(Dir4::from_char('v'), Some(Dir4::Down));
+        assert_eq!(Dir4::from_char('L'), Some(Dir4::Left));
+        assert_eq!(Dir4::from_char('.'), None);
+    }
+}
@@ -0,0 +1,475 @@
+/// Graphs over named or numbered nodes.
+///
+/// Node names are interned into dense [`NodeId`]s, puzzles that number their nodes themselves can
+/// use those numbers as ids directly with [`Graph::from_ids`]. Edges are stored in a compressed adjacency
+/// list: the sorted neighbours of all nodes live in one vector, sliced by per-node offsets.
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::interner::{Id, Interner};
+
+pub type NodeId = Id;
+
+#[derive(Debug, Clone)]
+pub struct Graph {
+    directed: bool,
+    len: usize,
+    names: Interner,
+    offsets: Vec<usize>,
+    neighbours: Vec<NodeId>,
+}
+
+/// Collects nodes and edges for a [`Graph`].
+#[derive(Debug, Clone, Default)]
+pub struct GraphBuilder {
+    directed: bool,
+    len: usize,
+    names: Interner,
+    edges: Vec<(NodeId, NodeId)>,
+}
+
+impl GraphBuilder {
+    /// Adds a node without edges, returns its id.
+    pub fn node(&mut self, name: &str) -> NodeId {
+        self.names.intern(name)
+    }
+
+    /// Adds an edge, implicitly adding its nodes.
+    pub fn edge(&mut self, from: &str, to: &str) -> &mut Self {
+        let from = self.node(from);
+        let to = self.node(to);
+        self.edge_ids(from, to)
+    }
+
+    fn edge_ids(&mut self, from: NodeId, to: NodeId) -> &mut Self {
+        self.edges.push((from, to));
+        if !self.directed {
+            self.edges.push((to, from));
+        }
+        self
+    }
+
+    pub fn build(mut self) -> Graph {
+        self.edges.sort_unstable();
+        self.edges.dedup();
+
+        let len = self.len.max(self.names.len());
+        let mut offsets = vec![0; len + 1];
+        for &(from, _) in &self.edges {
+            offsets[from as usize + 1] += 1;
+        }
+        for i in 1..offsets.len() {
+            offsets[i] += offsets[i - 1];
+        }
+
+        Graph {
+            directed: self.directed,
+            len,
+            names: self.names,
+            offsets,
+            neighbours: self.edges.into_iter().map(|(_, to)| to).collect(),
+        }
+    }
+}
+
+/// The error returned by [`Graph::topological_sort`], holds the nodes of one cycle in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError {
+    pub cycle: Vec<NodeId>,
+}
+
+impl Error for CycleError {}
+
+impl Display for CycleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cycle: Vec<String> = self.cycle.iter().map(ToString::to_string).collect();
+        write!(f, "graph contains a cycle: {}", cycle.join(" -> "))
+    }
+}
+
+impl Graph {
+    pub fn builder(directed: bool) -> GraphBuilder {
+        GraphBuilder {
+            directed,
+            ..GraphBuilder::default()
+        }
+    }
+
+    /// Builds an undirected graph from `(a, b)` edges.
+    pub fn undirected<'a>(edges: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        Self::from_edges(false, edges)
+    }
+
+    /// Builds a directed graph from `(from, to)` edges.
+    pub fn directed<'a>(edges: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        Self::from_edges(true, edges)
+    }
+
+    fn from_edges<'a>(directed: bool, edges: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let mut builder = Self::builder(directed);
+        for (from, to) in edges {
+            builder.edge(from, to);
+        }
+        builder.build()
+    }
+
+    /// Builds a graph over the nodes `0..len` from edges between their ids.
+    /// These nodes have no names, so [`Graph::name`] and [`Graph::id`] do not apply.
+    ///
+    /// # Panics
+    /// Panics if an edge refers to an id of `len` or above.
+    pub fn from_ids(
+        directed: bool,
+        len: usize,
+        edges: impl IntoIterator<Item = (NodeId, NodeId)>,
+    ) -> Self {
+        let mut builder = GraphBuilder {
+            len,
+            ..Self::builder(directed)
+        };
+        for (from, to) in edges {
+            assert!(
+                (from as usize) < len && (to as usize) < len,
+                "edge {from} -> {to} is not within {len} nodes"
+            );
+            builder.edge_ids(from, to);
+        }
+        builder.build()
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        0..self.len() as NodeId
+    }
+
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.names.get(name)
+    }
+
+    /// # Panics
+    /// Panics if the node has no name, see [`Graph::from_ids`].
+    pub fn name(&self, id: NodeId) -> &str {
+        self.names.resolve(id)
+    }
+
+    /// The node names, e.g. to query them by prefix.
+    pub fn names(&self) -> &Interner {
+        &self.names
+    }
+
+    /// Outgoing neighbours of a node, sorted by id.
+    pub fn neighbours(&self, id: NodeId) -> &[NodeId] {
+        let id = id as usize;
+        &self.neighbours[self.offsets[id]..self.offsets[id + 1]]
+    }
+
+    pub fn degree(&self, id: NodeId) -> usize {
+        self.neighbours(id).len()
+    }
+
+    pub fn has_edge(&self, from: NodeId, to: NodeId) -> bool {
+        self.neighbours(from).binary_search(&to).is_ok()
+    }
+
+    /* ------------------------------------------------------------------------ */
+
+    /// All triangles `[a, b, c]` with `a < b < c`, expects an undirected graph.
+    pub fn triangles(&self) -> Vec<[NodeId; 3]> {
+        let mut triangles = vec![];
+        for a in self.nodes() {
+            let higher = |id: NodeId| self.neighbours(id).iter().copied().filter(move |&n| n > id);
+            for b in higher(a) {
+                for c in higher(b) {
+                    if self.has_edge(a, c) {
+                        triangles.push([a, b, c]);
+                    }
+                }
+            }
+        }
+        triangles
+    }
+
+    /// A largest set of pairwise connected nodes, sorted by id.
+    /// Uses Bron–Kerbosch with pivoting, so expects an undirected graph.
+    pub fn maximum_clique(&self) -> Vec<NodeId> {
+        let mut best = vec![];
+        self.bron_kerbosch(&mut vec![], self.nodes().collect(), vec![], &mut best);
+        best.sort_unstable();
+        best
+    }
+
+    fn bron_kerbosch(
+        &self,
+        clique: &mut Vec<NodeId>,
+        candidates: Vec<NodeId>,
+        mut excluded: Vec<NodeId>,
+        best: &mut Vec<NodeId>,
+    ) {
+        if candidates.is_empty() {
+            if excluded.is_empty() && clique.len() > best.len() {
+                best.clone_from(clique);
+            }
+            return;
+        }
+        if clique.len() + candidates.len() <= best.len() {
+            return;
+        }
+
+        let pivot = candidates
+            .iter()
+            .chain(&excluded)
+            .copied()
+            .max_by_key(|&p| self.degree(p))
+            .unwrap_or_default();
+
+        let mut candidates = candidates;
+        let branches: Vec<NodeId> = candidates
+            .iter()
+            .copied()
+            .filter(|&v| !self.has_edge(pivot, v))
+            .collect();
+
+        for v in branches {
+            let keep = |set: &[NodeId]| -> Vec<NodeId> {
+                set.iter()
+                    .copied()
+                    .filter(|&n| self.has_edge(v, n))
+                    .collect()
+            };
+            clique.push(v);
+            self.bron_kerbosch(clique, keep(&candidates), keep(&excluded), best);
+            clique.pop();
+
+            candidates.retain(|&n| n != v);
+            excluded.push(v);
+        }
+    }
+
+    /// Groups nodes into connected components, treating edges as undirected.
+    /// Components are sorted by their smallest node, and nodes within by id.
+    pub fn connected_components(&self) -> Vec<Vec<NodeId>> {
+        let mut undirected: Vec<Vec<NodeId>> = self
+            .nodes()
+            .map(|id| self.neighbours(id).to_vec())
+            .collect();
+        if self.directed {
+            for from in self.nodes() {
+                for &to in self.neighbours(from) {
+                    undirected[to as usize].push(from);
+                }
+            }
+        }
+
+        let mut component = vec![usize::MAX; self.len()];
+        let mut components: Vec<Vec<NodeId>> = vec![];
+
+        for start in self.nodes() {
+            if component[start as usize] != usize::MAX {
+                continue;
+            }
+            let index = components.len();
+            component[start as usize] = index;
+            let mut nodes = vec![start];
+            let mut stack = vec![start];
+
+            while let Some(node) = stack.pop() {
+                for &next in &undirected[node as usize] {
+                    if component[next as usize] == usize::MAX {
+                        component[next as usize] = index;
+                        nodes.push(next);
+                        stack.push(next);
+                    }
+                }
+            }
+            nodes.sort_unstable();
+            components.push(nodes);
+        }
+        components
+    }
+
+    /// Orders the nodes such that every edge points forward, preferring smaller ids on ties.
+    /// Errors with one of the cycles if there is no such order.
+    pub fn topological_sort(&self) -> Result<Vec<NodeId>, CycleError> {
+        let mut in_degree = vec![0; self.len()];
+        for &to in &self.neighbours {
+            in_degree[to as usize] += 1;
+        }
+
+        let mut ready: BinaryHeap<Reverse<NodeId>> = self
+            .nodes()
+            .filter(|&id| in_degree[id as usize] == 0)
+            .map(Reverse)
+            .collect();
+        let mut order = Vec::with_capacity(self.len());
+
+        while let Some(Reverse(node)) = ready.pop() {
+            order.push(node);
+            for &next in self.neighbours(node) {
+                in_degree[next as usize] -= 1;
+                if in_degree[next as usize] == 0 {
+                    ready.push(Reverse(next));
+                }
+            }
+        }
+
+        if order.len() == self.len() {
+            return Ok(order);
+        }
+
+        // every remaining node has a remaining predecessor, so walking backwards has to repeat.
+        let mut predecessor = vec![None; self.len()];
+        for from in self.nodes().filter(|&id| in_degree[id as usize] > 0) {
+            for &to in self.neighbours(from) {
+                if in_degree[to as usize] > 0 {
+                    predecessor[to as usize] = Some(from);
+                }
+            }
+        }
+        let Some(start) = self.nodes().find(|&id| in_degree[id as usize] > 0) else {
+            unreachable!("unsorted nodes have a positive in-degree");
+        };
+
+        let mut seen = vec![false; self.len()];
+        let mut node = start;
+        while !seen[node as usize] {
+            seen[node as usize] = true;
+            node = predecessor[node as usize].unwrap_or(node);
+        }
+
+        let mut cycle = vec![node];
+        let mut current = predecessor[node as usize].unwrap_or(node);
+        while current != node {
+            cycle.push(current);
+            current = predecessor[current as usize].unwrap_or(node);
+        }
+        cycle.reverse();
+        Err(CycleError { cycle })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{CycleError, Graph};
+
+    static EDGES: &str = "a-b\nb-c\nc-a\nc-d\nd-b\ne-f\nb-e";
+
+    fn get_mock_graph() -> Graph {
+        Graph::undirected(EDGES.lines().filter_map(|line| line.split_once('-')))
+    }
+
+    fn names(graph: &Graph, ids: &[u32]) -> Vec<String> {
+        ids.iter().map(|&id| graph.name(id).to_owned()).collect()
+    }
+
+    #[test]
+    fn builds_adjacency() {
+        let graph = get_mock_graph();
+        let b = graph.id("b").unwrap();
+
+        assert_eq!(graph.len(), 6);
+        assert_eq!(names(&graph, graph.neighbours(b)), ["a", "c", "d", "e"]);
+        assert!(graph.has_edge(graph.id("f").unwrap(), graph.id("e").unwrap()));
+        assert!(!graph.has_edge(graph.id("a").unwrap(), graph.id("d").unwrap()));
+        assert_eq!(graph.id("g"), None);
+    }
+
+    #[test]
+    fn finds_triangles_and_cliques() {
+        let graph = get_mock_graph();
+        let triangles: Vec<Vec<String>> = graph
+            .triangles()
+            .iter()
+            .map(|triangle| names(&graph, triangle))
+            .collect();
+        assert_eq!(triangles, [["a", "b", "c"], ["b", "c", "d"]]);
+
+        let mut builder = Graph::builder(false);
+        for (a, b) in [
+            ("a", "b"),
+            ("a", "c"),
+            ("a", "d"),
+            ("b", "c"),
+            ("b", "d"),
+            ("c", "d"),
+        ] {
+            builder.edge(a, b);
+        }
+        builder.edge("d", "e").edge("e", "a");
+        let graph = builder.build();
+        assert_eq!(names(&graph, &graph.maximum_clique()), ["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn finds_components() {
+        let mut builder = Graph::builder(true);
+        builder.edge("a", "b").edge("c", "b").edge("d", "e");
+        builder.node("f");
+        let graph = builder.build();
+
+        let components: Vec<Vec<String>> = graph
+            .connected_components()
+            .iter()
+            .map(|component| names(&graph, component))
+            .collect();
+        assert_eq!(components, [vec!["a", "b", "c"], vec!["d", "e"], vec!["f"]]);
+    }
+
+    #[test]
+    fn sorts_topologically() {
+        let graph = Graph::directed([
+            ("shirt", "tie"),
+            ("tie", "jacket"),
+            ("pants", "shoes"),
+            ("pants", "jacket"),
+        ]);
+        let order = graph.topological_sort().unwrap();
+        let position = |name: &str| order.iter().position(|&id| graph.name(id) == name).unwrap();
+
+        assert_eq!(order.len(), 5);
+        assert!(position("shirt") < position("tie"));
+        assert!(position("tie") < position("jacket"));
+        assert!(position("pants") < position("jacket"));
+        assert!(position("pants") < position("shoes"));
+    }
+
+    #[test]
+    fn reports_cycles() {
+        let graph = Graph::directed([("a", "b"), ("b", "c"), ("c", "d"), ("d", "b")]);
+        let Err(CycleError { cycle }) = graph.topological_sort() else {
+            panic!("expected a cycle");
+        };
+
+        let mut cycle = names(&graph, &cycle);
+        cycle.sort();
+        assert_eq!(cycle, ["b", "c", "d"]);
+    }
+
+    #[test]
+    fn builds_from_ids() {
+        let graph = Graph::from_ids(true, 4, [(2, 0), (0, 1), (2, 1)]);
+        assert_eq!(graph.len(), 4);
+        assert_eq!(graph.neighbours(2), [0, 1]);
+        assert_eq!(graph.topological_sort(), Ok(vec![2, 0, 1, 3]));
+
+        let graph = Graph::from_ids(false, 2, [(0, 1)]);
+        assert!(graph.has_edge(1, 0));
+
+        let graph = Graph::from_ids(true, 3, [(0, 1), (1, 2), (2, 1)]);
+        assert!(graph.topological_sort().is_err());
+    }
+}
@@ -0,0 +1,114 @@
+/// Interning of short puzzle identifiers like node or wire names.
+///
+/// Every distinct name gets a dense id in insertion order, so ids can index plain vectors.
+use std::collections::HashMap;
+
+pub type Id = u32;
+
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    ids: HashMap<String, Id>,
+    names: Vec<String>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the id of `name`, assigning the next free id if it is new.
+    pub fn intern(&mut self, name: &str) -> Id {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.names.len() as Id;
+        self.names.push(name.to_owned());
+        self.ids.insert(name.to_owned(), id);
+        id
+    }
+
+    /// Returns the id of `name` without interning it.
+    pub fn get(&self, name: &str) -> Option<Id> {
+        self.ids.get(name).copied()
+    }
+
+    /// Returns the name of `id`.
+    ///
+    /// # Panics
+    /// Panics if `id` was not handed out by this interner.
+    pub fn resolve(&self, id: Id) -> &str {
+        &self.names[id as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// All ids with their names, in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (Id, &str)> {
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(id, name)| (id as Id, name.as_str()))
+    }
+
+    /// All ids whose name starts with `prefix`, in insertion order.
+    pub fn with_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = (Id, &'a str)> {
+        self.iter()
+            .filter(move |(_, name)| name.starts_with(prefix))
+    }
+
+    /// All ids whose name is `prefix` followed by a number, sorted by that number.
+    /// E.g. `numbered("z")` yields `z00`, `z01`, ... as `(0, id)`, `(1, id)`, ...
+    pub fn numbered(&self, prefix: &str) -> Vec<(u32, Id)> {
+        let mut numbered: Vec<(u32, Id)> = self
+            .with_prefix(prefix)
+            .filter_map(|(id, name)| Some((name[prefix.len()..].parse().ok()?, id)))
+            .collect();
+        numbered.sort_unstable();
+        numbered
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Interner;
+
+    #[test]
+    fn interns_names() {
+        let mut interner = Interner::new();
+        let ta = interner.intern("ta");
+        let kh = interner.intern("kh");
+
+        assert_eq!((ta, kh), (0, 1));
+        assert_eq!(interner.intern("ta"), ta);
+        assert_eq!(interner.get("kh"), Some(kh));
+        assert_eq!(interner.get("co"), None);
+        assert_eq!(interner.resolve(kh), "kh");
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn queries_prefixes_and_numbers() {
+        let mut interner = Interner::new();
+        for name in ["z02", "x00", "z00", "zed", "z10", "z01", "tz1"] {
+            interner.intern(name);
+        }
+
+        let prefixed: Vec<&str> = interner.with_prefix("z").map(|(_, name)| name).collect();
+        assert_eq!(prefixed, ["z02", "z00", "zed", "z10", "z01"]);
+
+        let numbered: Vec<(u32, &str)> = interner
+            .numbered("z")
+            .into_iter()
+            .map(|(n, id)| (n, interner.resolve(id)))
+            .collect();
+        assert_eq!(numbered, [(0, "z00"), (1, "z01"), (2, "z02"), (10, "z10")]);
+    }
+}
@@ -1,3 +1,22 @@
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
+pub mod counter;
+pub mod cycle;
+pub mod direction;
+pub mod dsu;
+pub mod graph;
+pub mod grid;
+pub mod interner;
+pub mod math;
+pub mod memo;
+pub mod parse;
+pub mod point;
+pub mod queue;
+pub mod region;
+pub mod search;
+
+pub use direction::{Dir4, Dir8};
+pub use grid::Grid;
+pub use interner::Interner;
+pub use point::{Point, Vec2};
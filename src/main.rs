@@ -1,4 +1,6 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, read, readme, scaffold, solve, status, time,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -7,7 +9,8 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::queue::QueueKind;
+    use advent_of_code::template::{export::ExportFormat, Day};
     use std::process;
 
     pub enum AppArguments {
@@ -17,6 +20,10 @@ mod args {
         Read {
             day: Day,
         },
+        Readme,
+        Status {
+            store: bool,
+        },
         Scaffold {
             day: Day,
             download: bool,
@@ -35,6 +42,9 @@ mod args {
             all: bool,
             day: Option<Day>,
             store: bool,
+            compare: bool,
+            export: Option<ExportFormat>,
+            queue: Option<QueueKind>,
         },
         #[cfg(feature = "today")]
         Today,
@@ -50,11 +60,17 @@ mod args {
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let compare = args.contains("--compare");
+                let export = args.opt_value_from_str("--export")?;
+                let queue = args.opt_value_from_str("--queue")?;
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    compare,
+                    export,
+                    queue,
                 }
             }
             Some("download") => AppArguments::Download {
@@ -63,6 +79,10 @@ mod args {
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
+            Some("readme") => AppArguments::Readme,
+            Some("status") => AppArguments::Status {
+                store: args.contains("--store"),
+            },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
@@ -103,10 +123,23 @@ fn main() {
         }
         Ok(args) => match args {
             AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                compare,
+                export,
+                queue,
+            } => time::handle(day, all, store, compare, export, queue),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download, overwrite } => {
+            AppArguments::Readme => readme::handle(),
+            AppArguments::Status { store } => status::handle(store),
+            AppArguments::Scaffold {
+                day,
+                download,
+                overwrite,
+            } => {
                 scaffold::handle(day, overwrite);
                 if download {
                     download::handle(day);
@@ -0,0 +1,365 @@
+/// Number theory and exact linear algebra.
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// The greatest common divisor, always non-negative.
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, always non-negative.
+pub fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+/// Returns `(g, x, y)` with `a * x + b * y = g`, where `g` is the non-negative gcd.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = extended_gcd_wide(a.into(), b.into());
+    (g as i64, x as i64, y as i64)
+}
+
+/// [`extended_gcd`] in `i128`, so that products of two `i64` values do not overflow.
+fn extended_gcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The inverse of `a` modulo `m` in `0..m`, [`None`] if `a` and `m` are not coprime or `m` is not
+/// positive.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    if m <= 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// Solves `x ≡ r (mod m)` for all `(r, m)` pairs, the moduli need not be coprime.
+/// Returns `(x, lcm)` with `x` in `0..lcm`, or [`None`] if the congruences contradict each other,
+/// a modulus is not positive or the lcm does not fit into `i64`.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut x: i128 = 0;
+    let mut modulus: i128 = 1;
+
+    for &(r, m) in congruences {
+        if m <= 0 {
+            return None;
+        }
+        let (r, m) = (i128::from(r).rem_euclid(m.into()), i128::from(m));
+        let (g, p, _) = extended_gcd_wide(modulus, m);
+        if (r - x) % g != 0 {
+            return None;
+        }
+        // x + modulus * k ≡ r (mod m)  =>  k ≡ (r - x) / g * p (mod m / g)
+        let step = m / g;
+        let k = ((r - x) / g * p).rem_euclid(step);
+        x += modulus * k;
+        modulus *= step;
+        // NOTE: bounding the modulus by i64 keeps the products above within i128.
+        i64::try_from(modulus).ok()?;
+        x = x.rem_euclid(modulus);
+    }
+    Some((x as i64, modulus as i64))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An exact rational number, always kept in lowest terms with a positive denominator.
+///
+/// # Display
+/// This value displays as `n` if it is an integer and `n/d` otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fraction {
+    num: i128,
+    den: i128,
+}
+
+impl Fraction {
+    /// # Panics
+    /// Panics if `den` is zero.
+    pub fn new(num: i128, den: i128) -> Self {
+        assert!(den != 0, "denominator must not be zero");
+        let g = gcd_i128(num, den).max(1) * den.signum();
+        Fraction {
+            num: num / g,
+            den: den / g,
+        }
+    }
+
+    pub fn numerator(self) -> i128 {
+        self.num
+    }
+
+    pub fn denominator(self) -> i128 {
+        self.den
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.num == 0
+    }
+
+    /// The value as an integer, [`None`] if it has a fractional part.
+    pub fn to_integer(self) -> Option<i128> {
+        (self.den == 1).then_some(self.num)
+    }
+}
+
+fn gcd_i128(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+impl From<i64> for Fraction {
+    fn from(value: i64) -> Self {
+        Fraction {
+            num: value as i128,
+            den: 1,
+        }
+    }
+}
+
+impl Display for Fraction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl Add for Fraction {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Fraction::new(self.num * rhs.den + rhs.num * self.den, self.den * rhs.den)
+    }
+}
+
+impl Sub for Fraction {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Mul for Fraction {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Fraction::new(self.num * rhs.num, self.den * rhs.den)
+    }
+}
+
+impl Div for Fraction {
+    type Output = Self;
+
+    /// # Panics
+    /// Panics if `rhs` is zero.
+    fn div(self, rhs: Self) -> Self::Output {
+        Fraction::new(self.num * rhs.den, self.den * rhs.num)
+    }
+}
+
+impl Neg for Fraction {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Fraction {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+impl PartialOrd for Fraction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Fraction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.num * other.den).cmp(&(other.num * self.den))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The solutions of a linear system, see [`solve_linear`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Solutions {
+    None,
+    One(Vec<Fraction>),
+    /// `particular` solves the system with every `free` variable set to zero.
+    Infinite {
+        particular: Vec<Fraction>,
+        free: Vec<usize>,
+    },
+}
+
+/// Solves `matrix * x = rhs` exactly with Gauss-Jordan elimination.
+///
+/// # Panics
+/// Panics if the rows of `matrix` differ in length or `rhs` has a different number of rows.
+pub fn solve_linear(matrix: &[Vec<i64>], rhs: &[i64]) -> Solutions {
+    assert_eq!(matrix.len(), rhs.len(), "every row needs a right-hand side");
+    let columns = matrix.first().map_or(0, Vec::len);
+
+    let mut rows: Vec<Vec<Fraction>> = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, &b)| {
+            assert_eq!(row.len(), columns, "rows differ in length");
+            row.iter().chain([&b]).map(|&v| Fraction::from(v)).collect()
+        })
+        .collect();
+
+    let mut pivots = vec![];
+    for column in 0..columns {
+        let rank = pivots.len();
+        let Some(pivot) = (rank..rows.len()).find(|&r| !rows[r][column].is_zero()) else {
+            continue;
+        };
+        rows.swap(rank, pivot);
+
+        let factor = rows[rank][column];
+        for value in rows[rank].iter_mut() {
+            *value = *value / factor;
+        }
+        let pivot_row = rows[rank].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            let factor = row[column];
+            if r != rank && !factor.is_zero() {
+                for (value, &pivot_value) in row.iter_mut().zip(&pivot_row).skip(column) {
+                    *value = *value - factor * pivot_value;
+                }
+            }
+        }
+        pivots.push(column);
+    }
+
+    if rows[pivots.len()..]
+        .iter()
+        .any(|row| !row[columns].is_zero())
+    {
+        return Solutions::None;
+    }
+
+    let mut particular = vec![Fraction::from(0); columns];
+    for (row, &column) in pivots.iter().enumerate() {
+        particular[column] = rows[row][columns];
+    }
+
+    if pivots.len() == columns {
+        Solutions::One(particular)
+    } else {
+        let free = (0..columns).filter(|c| !pivots.contains(c)).collect();
+        Solutions::Infinite { particular, free }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{crt, extended_gcd, gcd, lcm, mod_inverse, solve_linear, Fraction, Solutions};
+
+    #[test]
+    fn calculates_gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+
+        for (a, b) in [(240, 46), (-7, 3), (101, 103), (0, 4)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn calculates_modular_inverse() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(101, 103), Some(51));
+        assert_eq!(mod_inverse(4, 6), None);
+        assert_eq!(mod_inverse(3, 0), None);
+        assert_eq!(mod_inverse(3, -11), None);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(3, 4), (1, 6)]), Some((7, 12)));
+        assert_eq!(crt(&[(0, 4), (1, 6)]), None);
+        assert_eq!(crt(&[(-1, 101), (5, 103)]), Some((10099, 10403)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(1, 0)]), None);
+        assert_eq!(crt(&[(1, -5)]), None);
+        assert_eq!(
+            crt(&[(1, 2_000_000_011), (2, 3_000_000_019)]),
+            Some((2_400_000_027_200_000_078, 6_000_000_071_000_000_209))
+        );
+        assert_eq!(crt(&[(0, i64::MAX), (0, i64::MAX - 1)]), None);
+    }
+
+    #[test]
+    fn calculates_with_fractions() {
+        let half = Fraction::new(2, -4);
+        assert_eq!(half, Fraction::new(-1, 2));
+        assert_eq!(half.to_string(), "-1/2");
+        assert_eq!((half + Fraction::from(3)).to_string(), "5/2");
+        assert_eq!((half * Fraction::new(4, 3)).to_integer(), None);
+        assert_eq!((half / Fraction::new(-1, 4)).to_integer(), Some(2));
+        assert!(half < Fraction::from(0));
+    }
+
+    #[test]
+    fn solves_linear_systems() {
+        // Button A: X+94, Y+34; Button B: X+22, Y+67; Prize: X=8400, Y=5400
+        let one = solve_linear(&[vec![94, 22], vec![34, 67]], &[8400, 5400]);
+        assert_eq!(
+            one,
+            Solutions::One(vec![Fraction::from(80), Fraction::from(40)])
+        );
+
+        let none = solve_linear(&[vec![1, 2], vec![2, 4]], &[3, 5]);
+        assert_eq!(none, Solutions::None);
+
+        let infinite = solve_linear(&[vec![1, 2], vec![2, 4]], &[3, 6]);
+        assert_eq!(
+            infinite,
+            Solutions::Infinite {
+                particular: vec![Fraction::from(3), Fraction::from(0)],
+                free: vec![1],
+            }
+        );
+    }
+}
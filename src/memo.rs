@@ -0,0 +1,159 @@
+/// Memoization for recursive counting solutions.
+///
+/// The recursive function takes the [`Memo`] as a parameter and wraps its body in
+/// [`Memo::get_or_insert_with`], which hands the memo back to the closure for the recursive calls.
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    hits: usize,
+    misses: usize,
+}
+
+/// How well a [`Memo`] performed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: usize,
+    pub misses: usize,
+    pub entries: usize,
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses, {} entries",
+            self.hits, self.misses, self.entries
+        )
+    }
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo {
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached value for `key`, or computes it with `compute` and caches it.
+    ///
+    /// The key can be looked up in borrowed form, e.g. a `&[char]` for a `Vec<char>` key,
+    /// so it is only copied into an owned key on a miss.
+    pub fn get_or_insert_with<Q>(&mut self, key: &Q, compute: impl FnOnce(&mut Self) -> V) -> V
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        if let Some(value) = self.cache.get(key) {
+            self.hits += 1;
+            return value.clone();
+        }
+        self.misses += 1;
+        let value = compute(self);
+        self.cache.insert(key.to_owned(), value.clone());
+        value
+    }
+
+    /// Returns the cached value for `key` without computing anything.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.cache.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Forgets all cached values and resets the statistics.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    pub fn stats(&self) -> Stats {
+        Stats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.cache.len(),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Memo, Stats};
+
+    fn fibonacci(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get_or_insert_with(&n, |memo| fibonacci(n - 1, memo) + fibonacci(n - 2, memo))
+    }
+
+    #[test]
+    fn caches_recursive_calls() {
+        let mut memo = Memo::new();
+        assert_eq!(fibonacci(50, &mut memo), 12_586_269_025);
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 47,
+                misses: 49,
+                entries: 49
+            }
+        );
+        assert_eq!(memo.stats().to_string(), "47 hits, 49 misses, 49 entries");
+
+        assert_eq!(fibonacci(50, &mut memo), 12_586_269_025);
+        assert_eq!(memo.stats().hits, 48);
+        assert_eq!(memo.get(&10), Some(&55));
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.stats().misses, 0);
+    }
+
+    /// Number of ways to split `s` into the given words.
+    fn splits(s: &str, words: &[&str], memo: &mut Memo<String, u64>) -> u64 {
+        if s.is_empty() {
+            return 1;
+        }
+        memo.get_or_insert_with(s, |memo| {
+            words
+                .iter()
+                .filter_map(|word| s.strip_prefix(word))
+                .map(|rest| splits(rest, words, memo))
+                .sum()
+        })
+    }
+
+    #[test]
+    fn looks_up_borrowed_keys() {
+        let mut memo = Memo::new();
+        let words = ["r", "wr", "b", "g", "bwu", "rb", "gb", "br"];
+        assert_eq!(splits("rrbgbr", &words, &mut memo), 6);
+        assert_eq!(memo.get("gbr"), Some(&3));
+    }
+}
@@ -0,0 +1,254 @@
+/// Helpers for the usual puzzle input shapes: numbers buried in text, blank-line separated
+/// sections and one value per line.
+///
+/// Line numbers in errors start at 1, like in an editor.
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, message: impl Display) -> Self {
+        ParseError {
+            line,
+            message: message.to_string(),
+        }
+    }
+
+    /// Turns the line number of an error inside a section into one relative to the whole input,
+    /// `first_line` being the section's first line from [`sections_with_lines`].
+    pub fn in_section(self, first_line: usize) -> Self {
+        ParseError {
+            line: self.line + first_line - 1,
+            ..self
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// All integers in `s`, a `-` directly in front of digits makes the number negative.
+/// E.g. `p=0,4 v=3,-3` yields `0, 4, 3, -3`.
+/// A number that does not fit into `T` yields its parse error.
+pub fn ints<T: FromStr>(s: &str) -> impl Iterator<Item = Result<T, T::Err>> + '_ {
+    numbers(s, true)
+}
+
+/// All runs of digits in `s`, ignoring any signs.
+/// E.g. `Button A: X+94, Y+34` yields `94, 34`.
+/// A number that does not fit into `T` yields its parse error.
+pub fn uints<T: FromStr>(s: &str) -> impl Iterator<Item = Result<T, T::Err>> + '_ {
+    numbers(s, false)
+}
+
+/// Exactly `N` integers of `s` as an array, [`None`] if there are more or fewer or one of them
+/// does not fit into `T`.
+pub fn ints_array<const N: usize, T>(s: &str) -> Option<[T; N]>
+where
+    T: FromStr + Copy + Default,
+{
+    let mut array = [T::default(); N];
+    let mut numbers = ints(s);
+    for slot in array.iter_mut() {
+        *slot = numbers.next()?.ok()?;
+    }
+    numbers.next().is_none().then_some(array)
+}
+
+fn numbers<T: FromStr>(s: &str, signed: bool) -> impl Iterator<Item = Result<T, T::Err>> + '_ {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    std::iter::from_fn(move || {
+        while i < bytes.len() && !bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == bytes.len() {
+            return None;
+        }
+        let start = if signed && i > 0 && bytes[i - 1] == b'-' {
+            i - 1
+        } else {
+            i
+        };
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        Some(s[start..i].parse())
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The blocks of `input` separated by one or more blank lines, without their trailing newline.
+/// Lines containing only whitespace count as blank and `\r\n` line endings are fine.
+pub fn sections(input: &str) -> impl Iterator<Item = &str> {
+    sections_with_lines(input).map(|(_, section)| section)
+}
+
+/// Like [`sections`], but also yields the line number of the first line of every section,
+/// so errors inside a section can point at the right line.
+pub fn sections_with_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut sections = vec![];
+    let mut start: Option<(usize, usize)> = None;
+    let mut offset = 0;
+
+    for (number, line) in input.split_inclusive('\n').enumerate() {
+        let blank = line.trim().is_empty();
+        match (blank, start) {
+            (false, None) => start = Some((number + 1, offset)),
+            (true, Some((first_line, begin))) => {
+                sections.push((
+                    first_line,
+                    input[begin..offset].trim_end_matches(['\r', '\n']),
+                ));
+                start = None;
+            }
+            _ => {}
+        }
+        offset += line.len();
+    }
+    if let Some((first_line, begin)) = start {
+        sections.push((first_line, input[begin..].trim_end_matches(['\r', '\n'])));
+    }
+    sections.into_iter()
+}
+
+/// Parses every line of `input` as a `T`.
+pub fn lines_as<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    lines_with(input, str::parse)
+}
+
+/// Parses every line of `input` with `parse`, errors get the line number attached.
+pub fn lines_with<'a, T, E: Display>(
+    input: &'a str,
+    mut parse: impl FnMut(&'a str) -> Result<T, E>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse(line).map_err(|e| ParseError::new(i + 1, e)))
+        .collect()
+}
+
+/// Splits `line` at the first `separator` into a trimmed key and value.
+/// E.g. `key_value("x00: 1", ":")` is `Some(("x00", "1"))`.
+pub fn key_value<'a>(line: &'a str, separator: &str) -> Option<(&'a str, &'a str)> {
+    let (key, value) = line.split_once(separator)?;
+    Some((key.trim(), value.trim()))
+}
+
+/// [`key_value`] for every line of `input`.
+pub fn key_values<'a>(
+    input: &'a str,
+    separator: &str,
+) -> Result<Vec<(&'a str, &'a str)>, ParseError> {
+    lines_with(input, |line| {
+        key_value(line, separator).ok_or_else(|| format!("expected `{separator}` in {line:?}"))
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        ints, ints_array, key_value, key_values, lines_as, lines_with, sections,
+        sections_with_lines, uints, ParseError,
+    };
+
+    #[test]
+    fn extracts_numbers() {
+        let signed: Result<Vec<i64>, _> = ints("p=0,4 v=3,-3").collect();
+        assert_eq!(signed, Ok(vec![0, 4, 3, -3]));
+
+        let unsigned: Result<Vec<u32>, _> = uints("Button A: X+94, Y-34").collect();
+        assert_eq!(unsigned, Ok(vec![94, 34]));
+
+        let dashes: Result<Vec<i32>, _> = ints("1-2 --3 - 4").collect();
+        assert_eq!(dashes, Ok(vec![1, -2, -3, 4]));
+
+        assert_eq!(
+            ints_array::<2, i64>("Prize: X=8400, Y=5400"),
+            Some([8400, 5400])
+        );
+        assert_eq!(ints_array::<2, i64>("X=1, Y=2, Z=3"), None);
+        assert_eq!(ints_array::<2, i64>("X=1"), None);
+    }
+
+    #[test]
+    fn rejects_overflowing_numbers() {
+        let numbers: Vec<Result<u8, _>> = ints("255 256").collect();
+        assert_eq!(numbers[0], Ok(255));
+        assert_eq!(
+            numbers[1].as_ref().unwrap_err().to_string(),
+            "number too large to fit in target type"
+        );
+        assert_eq!(ints_array::<2, u8>("255 256"), None);
+    }
+
+    #[test]
+    fn splits_sections() {
+        let input = "a\nb\n\n\nc\r\n \r\nd\n";
+        let found: Vec<&str> = sections(input).collect();
+        assert_eq!(found, ["a\nb", "c", "d"]);
+
+        let lines: Vec<usize> = sections_with_lines(input).map(|(line, _)| line).collect();
+        assert_eq!(lines, [1, 5, 7]);
+
+        assert_eq!(sections("").count(), 0);
+        assert_eq!(sections("\n\n").count(), 0);
+    }
+
+    #[test]
+    fn parses_lines() {
+        assert_eq!(lines_as::<u32>("1\n2\n3\n"), Ok(vec![1, 2, 3]));
+
+        let error = lines_as::<u32>("1\nx\n3").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.to_string(), "line 2: invalid digit found in string");
+
+        let lengths = lines_with("hello\nworld!", |line| Ok::<_, String>(line.len()));
+        assert_eq!(lengths, Ok(vec![5, 6]));
+    }
+
+    #[test]
+    fn splits_keys_and_values() {
+        assert_eq!(key_value("x00: 1", ":"), Some(("x00", "1")));
+        assert_eq!(
+            key_value("x00 AND y00 -> z00", "->"),
+            Some(("x00 AND y00", "z00"))
+        );
+        assert_eq!(key_value("47|53", ","), None);
+
+        assert_eq!(
+            key_values("47|53\n97|13", "|"),
+            Ok(vec![("47", "53"), ("97", "13")])
+        );
+        assert_eq!(
+            key_values("47|53\n97-13", "|"),
+            Err(ParseError::new(2, "expected `|` in \"97-13\""))
+        );
+
+        let input = "x00: 1\n\nx00 AND y00 -> z00\nx00 OR y00";
+        let (first_line, gates) = sections_with_lines(input).nth(1).unwrap();
+        let error = key_values(gates, "->").unwrap_err().in_section(first_line);
+        assert_eq!(error.line, 4);
+    }
+}
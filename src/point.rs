@@ -0,0 +1,192 @@
+/// Points and vectors in two dimensions.
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point (or vector) in two dimensions.
+///
+/// When used with a [`crate::Grid`], `x` is the column and `y` is the row.
+///
+/// # Display
+/// This value displays as `(x, y)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A signed vector, e.g. a velocity or an offset between two points.
+pub type Vec2 = Point<i64>;
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// Applies `f` to both coordinates.
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Point<U> {
+        Point {
+            x: f(self.x),
+            y: f(self.y),
+        }
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    /// Converts an `(x, y)` tuple.
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+macro_rules! impl_integer_point {
+    ($($t:ty => $unsigned:ty),*) => {$(
+        impl Point<$t> {
+            /// The Manhattan (taxicab) distance between two points.
+            pub fn manhattan(self, other: Self) -> $unsigned {
+                self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+            }
+
+            /// Wraps the point into `0..size.x` and `0..size.y`, like on a torus.
+            #[must_use]
+            pub fn rem_euclid(self, size: Self) -> Self {
+                Self::new(self.x.rem_euclid(size.x), self.y.rem_euclid(size.y))
+            }
+
+            /// Converts a `(row, column)` grid position, returns [`None`] if it does not fit.
+            pub fn from_grid((row, col): (usize, usize)) -> Option<Self> {
+                Some(Self::new(col.try_into().ok()?, row.try_into().ok()?))
+            }
+
+            /// Converts into a `(row, column)` grid position, returns [`None`] if a coordinate is negative.
+            pub fn to_grid(self) -> Option<(usize, usize)> {
+                Some((self.y.try_into().ok()?, self.x.try_into().ok()?))
+            }
+
+            /// Converts an index into row-major cells of the given width.
+            pub fn from_index(index: usize, width: usize) -> Option<Self> {
+                Self::from_grid((index / width, index % width))
+            }
+
+            /// Converts into an index into row-major cells, returns [`None`] if outside of `width` x `height`.
+            pub fn to_index(self, width: usize, height: usize) -> Option<usize> {
+                let (row, col) = self.to_grid()?;
+                (row < height && col < width).then_some(row * width + col)
+            }
+        }
+    )*};
+}
+
+impl_integer_point!(
+    i32 => u32,
+    i64 => u64,
+    isize => usize,
+    u32 => u32,
+    u64 => u64,
+    usize => usize
+);
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Point, Vec2};
+
+    #[test]
+    fn supports_arithmetic() {
+        let a = Vec2::new(1, 2);
+        let b = Vec2::new(-3, 5);
+        assert_eq!(a + b, Vec2::new(-2, 7));
+        assert_eq!(a - b, Vec2::new(4, -3));
+        assert_eq!(b * 3, Vec2::new(-9, 15));
+        assert_eq!(-a, Vec2::new(-1, -2));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+    }
+
+    #[test]
+    fn calculates_manhattan_distance() {
+        assert_eq!(Vec2::new(1, 2).manhattan(Vec2::new(-3, 5)), 7);
+        assert_eq!(Point::<usize>::new(4, 0).manhattan(Point::new(1, 2)), 5);
+    }
+
+    #[test]
+    fn wraps_around() {
+        let size = Vec2::new(11, 7);
+        assert_eq!(Vec2::new(-1, 8).rem_euclid(size), Vec2::new(10, 1));
+        assert_eq!(
+            (Vec2::new(2, 4) + Vec2::new(2, -3) * 5).rem_euclid(size),
+            Vec2::new(1, 3)
+        );
+    }
+
+    #[test]
+    fn converts_grid_positions() {
+        let point = Vec2::from_grid((3, 5)).unwrap();
+        assert_eq!(point, Vec2::new(5, 3));
+        assert_eq!(point.to_grid(), Some((3, 5)));
+        assert_eq!(Vec2::new(-1, 0).to_grid(), None);
+
+        assert_eq!(point.to_index(10, 4), Some(35));
+        assert_eq!(point.to_index(5, 4), None);
+        assert_eq!(Vec2::from_index(35, 10), Some(point));
+    }
+}
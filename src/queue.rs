@@ -0,0 +1,357 @@
+/// Priority queues for the searches in [`crate::search`].
+///
+/// A [`BinaryHeap`] works for any costs. Puzzles with small integer edge costs can use a
+/// [`BucketQueue`] (Dial's algorithm) instead, and puzzles whose edges cost either zero or one can
+/// use a [`ZeroOneDeque`] (0-1 BFS). The latter relies on monotone pushes, i.e. an item is never
+/// pushed with a lower priority than the last popped item, which holds for Dijkstra's algorithm.
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Environment variable that selects the queue used by [`Queue::from_env_or`].
+/// `cargo time --queue <kind>` sets it for the benched solutions.
+pub const QUEUE_ENV: &str = "AOC_QUEUE";
+
+/// A min-priority queue.
+pub trait Frontier<C, T> {
+    fn push(&mut self, priority: C, item: T);
+    /// Removes an item with the lowest priority.
+    fn pop(&mut self) -> Option<(C, T)>;
+}
+
+impl<C: Ord, T: Ord> Frontier<C, T> for BinaryHeap<Reverse<(C, T)>> {
+    fn push(&mut self, priority: C, item: T) {
+        BinaryHeap::push(self, Reverse((priority, item)));
+    }
+
+    fn pop(&mut self) -> Option<(C, T)> {
+        BinaryHeap::pop(self).map(|Reverse(entry)| entry)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A bucket queue for integer priorities.
+/// Only keeps buckets between the lowest and highest queued priority, so with Dijkstra's algorithm
+/// memory is bounded by the largest edge cost rather than by the largest total cost.
+#[derive(Debug, Clone)]
+pub struct BucketQueue<C, T> {
+    buckets: VecDeque<Vec<(C, T)>>,
+    base: usize,
+    len: usize,
+}
+
+impl<C, T> BucketQueue<C, T> {
+    pub fn new() -> Self {
+        BucketQueue {
+            buckets: VecDeque::new(),
+            base: 0,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<C, T> Default for BucketQueue<C, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Copy + TryInto<usize>, T> Frontier<C, T> for BucketQueue<C, T> {
+    /// # Panics
+    /// Panics if `priority` does not fit into `usize`.
+    fn push(&mut self, priority: C, item: T) {
+        let Ok(key) = priority.try_into() else {
+            panic!("bucket queue priorities have to fit into usize");
+        };
+        if self.len == 0 {
+            self.buckets.clear();
+            self.base = key;
+        }
+        while key < self.base {
+            self.buckets.push_front(Vec::new());
+            self.base -= 1;
+        }
+
+        let offset = key - self.base;
+        if offset >= self.buckets.len() {
+            self.buckets.resize_with(offset + 1, Vec::new);
+        }
+        self.buckets[offset].push((priority, item));
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<(C, T)> {
+        while let Some(bucket) = self.buckets.front_mut() {
+            if let Some(entry) = bucket.pop() {
+                self.len -= 1;
+                return Some(entry);
+            }
+            self.buckets.pop_front();
+            self.base += 1;
+        }
+        None
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A deque for edges costing either zero or one.
+/// Items that are not more expensive than the front of the deque are pushed to the front, all
+/// others to the back.
+#[derive(Debug, Clone)]
+pub struct ZeroOneDeque<C, T> {
+    deque: VecDeque<(C, T)>,
+}
+
+impl<C, T> ZeroOneDeque<C, T> {
+    pub fn new() -> Self {
+        ZeroOneDeque {
+            deque: VecDeque::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.deque.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.deque.is_empty()
+    }
+}
+
+impl<C, T> Default for ZeroOneDeque<C, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Ord + Copy + TryInto<usize>, T> Frontier<C, T> for ZeroOneDeque<C, T> {
+    fn push(&mut self, priority: C, item: T) {
+        if let Some(&(front, _)) = self.deque.front() {
+            if let (Ok(priority), Ok(front)) = (priority.try_into(), front.try_into()) {
+                debug_assert!(
+                    priority <= front + 1,
+                    "0-1 deque got priority {priority} with {front} at the front, edges must cost zero or one"
+                );
+            }
+        }
+        match self.deque.front() {
+            Some((front, _)) if priority <= *front => self.deque.push_front((priority, item)),
+            _ => self.deque.push_back((priority, item)),
+        }
+    }
+
+    fn pop(&mut self) -> Option<(C, T)> {
+        self.deque.pop_front()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The kind of queue to use, see [`Queue`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QueueKind {
+    #[default]
+    Heap,
+    Bucket,
+    ZeroOne,
+}
+
+#[derive(Debug)]
+pub struct QueueKindFromStrError(String);
+
+impl Error for QueueKindFromStrError {}
+
+impl Display for QueueKindFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown queue \"{}\", expected one of heap, bucket, 0-1",
+            self.0
+        )
+    }
+}
+
+impl FromStr for QueueKind {
+    type Err = QueueKindFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "heap" => Ok(QueueKind::Heap),
+            "bucket" | "dial" => Ok(QueueKind::Bucket),
+            "0-1" | "zero-one" | "deque" => Ok(QueueKind::ZeroOne),
+            _ => Err(QueueKindFromStrError(s.into())),
+        }
+    }
+}
+
+impl Display for QueueKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueueKind::Heap => write!(f, "heap"),
+            QueueKind::Bucket => write!(f, "bucket"),
+            QueueKind::ZeroOne => write!(f, "0-1"),
+        }
+    }
+}
+
+impl QueueKind {
+    /// Reads the kind from [`QUEUE_ENV`], [`None`] if it is unset.
+    pub fn from_env() -> Result<Option<Self>, QueueKindFromStrError> {
+        match std::env::var(QUEUE_ENV) {
+            Ok(s) => s.parse().map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Reads the kind from [`QUEUE_ENV`], falls back to `default` if unset.
+    ///
+    /// A [`ZeroOneDeque`] pops in the wrong order unless every edge costs zero or one, so it is
+    /// only picked if the caller passes `zero_one_weights`. Invalid or unsuitable values are
+    /// reported on stderr and fall back to `default` as well.
+    pub fn from_env_or(default: QueueKind, zero_one_weights: bool) -> Self {
+        match Self::from_env() {
+            Ok(Some(QueueKind::ZeroOne)) if !zero_one_weights => {
+                eprintln!("{QUEUE_ENV}: edges do not cost zero or one, using the {default} queue.");
+                default
+            }
+            Ok(kind) => kind.unwrap_or(default),
+            Err(e) => {
+                eprintln!("{QUEUE_ENV}: {e}, using the {default} queue.");
+                default
+            }
+        }
+    }
+}
+
+/// A queue whose kind is picked at runtime, e.g. to benchmark the kinds against each other.
+#[derive(Debug, Clone)]
+pub enum Queue<C, T> {
+    Heap(BinaryHeap<Reverse<(C, T)>>),
+    Bucket(BucketQueue<C, T>),
+    ZeroOne(ZeroOneDeque<C, T>),
+}
+
+impl<C: Ord, T: Ord> Queue<C, T> {
+    pub fn new(kind: QueueKind) -> Self {
+        match kind {
+            QueueKind::Heap => Queue::Heap(BinaryHeap::new()),
+            QueueKind::Bucket => Queue::Bucket(BucketQueue::new()),
+            QueueKind::ZeroOne => Queue::ZeroOne(ZeroOneDeque::new()),
+        }
+    }
+
+    /// Creates the queue selected by [`QUEUE_ENV`], see [`QueueKind::from_env_or`].
+    pub fn from_env_or(default: QueueKind, zero_one_weights: bool) -> Self {
+        Self::new(QueueKind::from_env_or(default, zero_one_weights))
+    }
+}
+
+impl<C: Ord + Copy + TryInto<usize>, T: Ord> Frontier<C, T> for Queue<C, T> {
+    fn push(&mut self, priority: C, item: T) {
+        match self {
+            Queue::Heap(queue) => Frontier::push(queue, priority, item),
+            Queue::Bucket(queue) => queue.push(priority, item),
+            Queue::ZeroOne(queue) => queue.push(priority, item),
+        }
+    }
+
+    fn pop(&mut self) -> Option<(C, T)> {
+        match self {
+            Queue::Heap(queue) => Frontier::pop(queue),
+            Queue::Bucket(queue) => queue.pop(),
+            Queue::ZeroOne(queue) => queue.pop(),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BucketQueue, Frontier, Queue, QueueKind, ZeroOneDeque};
+
+    fn drain(queue: &mut impl Frontier<u32, char>) -> Vec<u32> {
+        std::iter::from_fn(|| queue.pop().map(|(priority, _)| priority)).collect()
+    }
+
+    #[test]
+    fn pops_buckets_in_order() {
+        let mut queue = BucketQueue::new();
+        queue.push(5, 'a');
+        queue.push(1005, 'b');
+        queue.push(6, 'c');
+        assert_eq!(queue.pop(), Some((5, 'a')));
+        queue.push(5, 'd');
+        queue.push(7, 'e');
+        assert_eq!(queue.len(), 4);
+        assert_eq!(drain(&mut queue), [5, 6, 7, 1005]);
+        assert!(queue.is_empty());
+
+        // an empty queue starts over at any priority.
+        queue.push(1, 'f');
+        assert_eq!(queue.pop(), Some((1, 'f')));
+    }
+
+    #[test]
+    fn accepts_decreasing_priorities() {
+        let mut queue = BucketQueue::new();
+        queue.push(5, 'a');
+        queue.push(2, 'b');
+        assert_eq!(drain(&mut queue), [2, 5]);
+    }
+
+    #[test]
+    fn pops_zero_one_deque_in_order() {
+        let mut queue = ZeroOneDeque::new();
+        queue.push(0, 'a');
+        assert_eq!(queue.pop(), Some((0, 'a')));
+        queue.push(1, 'b');
+        queue.push(0, 'c');
+        queue.push(1, 'd');
+        assert_eq!(drain(&mut queue), [0, 1, 1]);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(debug_assertions)]
+    fn rejects_expensive_edges_in_zero_one_deque() {
+        let mut queue = ZeroOneDeque::new();
+        queue.push(0, 'a');
+        queue.push(2, 'b');
+    }
+
+    #[test]
+    fn parses_kinds() {
+        assert_eq!("heap".parse::<QueueKind>().unwrap(), QueueKind::Heap);
+        assert_eq!("bucket".parse::<QueueKind>().unwrap(), QueueKind::Bucket);
+        assert_eq!("0-1".parse::<QueueKind>().unwrap(), QueueKind::ZeroOne);
+        assert!("fibonacci".parse::<QueueKind>().is_err());
+
+        for kind in [QueueKind::Heap, QueueKind::Bucket, QueueKind::ZeroOne] {
+            assert_eq!(kind.to_string().parse::<QueueKind>().unwrap(), kind);
+        }
+    }
+
+    #[test]
+    fn dispatches_to_kind() {
+        for kind in [QueueKind::Heap, QueueKind::Bucket] {
+            let mut queue = Queue::new(kind);
+            for (priority, item) in [(3, 'a'), (1, 'b'), (2, 'c')] {
+                queue.push(priority, item);
+            }
+            assert_eq!(drain(&mut queue), [1, 2, 3]);
+        }
+    }
+}
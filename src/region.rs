@@ -0,0 +1,127 @@
+/// Labeling of connected regions of equal cells in a [`Grid`].
+use crate::dsu::DisjointSet;
+use crate::{Dir4, Grid};
+
+/// A maximal group of orthogonally connected cells with equal values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    /// `(row, column)` positions in row-major order.
+    pub cells: Vec<(usize, usize)>,
+    pub area: usize,
+    /// Number of cell edges bordering another region or the outside of the grid.
+    pub perimeter: usize,
+    /// Number of straight fence segments, which equals the number of corners.
+    pub sides: usize,
+}
+
+/// Labels every cell with the index of its region.
+/// Regions are numbered in row-major order of their first cell.
+pub fn label<T: PartialEq>(grid: &Grid<T>) -> Grid<usize> {
+    let mut sets = DisjointSet::new(grid.len());
+    for (position, value) in grid.iter() {
+        for next in [(position.0, position.1 + 1), (position.0 + 1, position.1)] {
+            if grid.get(next) == Some(value) {
+                sets.union(grid.index_of(position), grid.index_of(next));
+            }
+        }
+    }
+
+    let mut labels = vec![usize::MAX; grid.len()];
+    let mut roots = vec![usize::MAX; grid.len()];
+    let mut next_label = 0;
+    for (index, label) in labels.iter_mut().enumerate() {
+        let root = sets.find(index);
+        if roots[root] == usize::MAX {
+            roots[root] = next_label;
+            next_label += 1;
+        }
+        *label = roots[root];
+    }
+
+    Grid::new(grid.width(), grid.height(), labels).unwrap()
+}
+
+/// All regions of the grid, in the order of [`label`].
+pub fn regions<T: PartialEq>(grid: &Grid<T>) -> Vec<Region> {
+    let labels = label(grid);
+    let count = labels.cells().iter().max().map_or(0, |max| max + 1);
+    let mut regions = vec![
+        Region {
+            cells: vec![],
+            area: 0,
+            perimeter: 0,
+            sides: 0,
+        };
+        count
+    ];
+
+    for (position, &label) in labels.iter() {
+        let same = |direction: (isize, isize)| {
+            labels
+                .offset(position, direction)
+                .is_some_and(|next| labels[next] == label)
+        };
+
+        let region = &mut regions[label];
+        region.cells.push(position);
+        region.area += 1;
+        region.perimeter += Dir4::ALL.iter().filter(|d| !same(d.offset())).count();
+
+        // every corner of a region is either convex (both sides leave the region) or concave
+        // (both sides stay in the region, but the diagonal does not).
+        for direction in Dir4::ALL {
+            let (a, b) = (direction.offset(), direction.turn_right().offset());
+            let diagonal = (a.0 + b.0, a.1 + b.1);
+            if (!same(a) && !same(b)) || (same(a) && same(b) && !same(diagonal)) {
+                region.sides += 1;
+            }
+        }
+    }
+    regions
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{label, regions};
+    use crate::Grid;
+
+    static GARDEN: &str = "\
+AAAA
+BBCD
+BBCC
+EEEC";
+
+    #[test]
+    fn labels_regions() {
+        let grid: Grid<char> = GARDEN.parse().unwrap();
+        let labels = label(&grid);
+        assert_eq!(labels.row(0), [0, 0, 0, 0]);
+        assert_eq!(labels.row(1), [1, 1, 2, 3]);
+        assert_eq!(labels.row(3), [4, 4, 4, 2]);
+    }
+
+    #[test]
+    fn measures_regions() {
+        let grid: Grid<char> = GARDEN.parse().unwrap();
+        let measures: Vec<(usize, usize, usize)> = regions(&grid)
+            .iter()
+            .map(|r| (r.area, r.perimeter, r.sides))
+            .collect();
+        assert_eq!(
+            measures,
+            [(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)]
+        );
+    }
+
+    #[test]
+    fn counts_sides_around_holes() {
+        let grid: Grid<char> = "XXX\nXOX\nXXX".parse().unwrap();
+        let regions = regions(&grid);
+        assert_eq!(regions[0].cells.len(), 8);
+        assert_eq!(regions[0].perimeter, 16);
+        assert_eq!(regions[0].sides, 8);
+        assert_eq!(regions[1].sides, 4);
+    }
+}
@@ -0,0 +1,408 @@
+/// Shortest-path searches over implicit graphs.
+///
+/// States are discovered lazily through a `successors` closure, so the same searches work for
+/// plain grid positions as well as richer states like `(position, direction)`.
+/// Every search records all optimal predecessors of a state, which allows reconstructing a single
+/// shortest path as well as enumerating every state that lies on any shortest path.
+/// Weighted searches take any [`Frontier`], see [`crate::queue`] for the available queues.
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use crate::queue::Frontier;
+
+/// Edge costs, `C::default()` is used as zero.
+pub trait Cost: Copy + Ord + Default + Add<Output = Self> {}
+
+impl<T: Copy + Ord + Default + Add<Output = T>> Cost for T {}
+
+/// The result of a search, see [`bfs`], [`dijkstra`] and [`astar`].
+///
+/// The search stops once every goal with the optimal cost has been settled. Costs of states that
+/// were reached but not settled by then are upper bounds only.
+#[derive(Debug, Clone)]
+pub struct Paths<S, C> {
+    states: Vec<S>,
+    indices: HashMap<S, usize>,
+    costs: Vec<C>,
+    predecessors: Vec<Vec<usize>>,
+    goals: Vec<usize>,
+}
+
+impl<S: Clone + Eq + Hash, C: Cost> Paths<S, C> {
+    fn new(start: S) -> Self {
+        Paths {
+            indices: HashMap::from([(start.clone(), 0)]),
+            states: vec![start],
+            costs: vec![C::default()],
+            predecessors: vec![vec![]],
+            goals: vec![],
+        }
+    }
+
+    /// Records the edge `from -> state`. Returns the index of `state` if its cost improved.
+    fn relax(&mut self, from: usize, state: S, cost: C) -> Option<usize> {
+        match self.indices.get(&state) {
+            Some(&index) if cost < self.costs[index] => {
+                self.costs[index] = cost;
+                self.predecessors[index] = vec![from];
+                Some(index)
+            }
+            Some(&index) => {
+                if cost == self.costs[index] && !self.predecessors[index].contains(&from) {
+                    self.predecessors[index].push(from);
+                }
+                None
+            }
+            None => {
+                let index = self.states.len();
+                self.indices.insert(state.clone(), index);
+                self.states.push(state);
+                self.costs.push(cost);
+                self.predecessors.push(vec![from]);
+                Some(index)
+            }
+        }
+    }
+
+    /// The cost of the cheapest goal, [`None`] if no goal was reachable.
+    pub fn cost(&self) -> Option<C> {
+        self.goals.first().map(|&index| self.costs[index])
+    }
+
+    /// All goals reached with the optimal cost, in the order they were settled.
+    pub fn goals(&self) -> impl Iterator<Item = &S> {
+        self.goals.iter().map(|&index| &self.states[index])
+    }
+
+    /// The cost of reaching `state`, [`None`] if it was never reached.
+    pub fn cost_to(&self, state: &S) -> Option<C> {
+        self.indices.get(state).map(|&index| self.costs[index])
+    }
+
+    /// Every reached state with its cost.
+    pub fn reached(&self) -> impl Iterator<Item = (&S, C)> {
+        self.states.iter().zip(self.costs.iter().copied())
+    }
+
+    /// All predecessors of `state` on some shortest path.
+    pub fn predecessors(&self, state: &S) -> impl Iterator<Item = &S> {
+        self.indices
+            .get(state)
+            .map(|&index| self.predecessors[index].as_slice())
+            .unwrap_or_default()
+            .iter()
+            .map(|&index| &self.states[index])
+    }
+
+    /// A shortest path from the start to the first goal, both inclusive.
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_from_index(*self.goals.first()?)
+    }
+
+    /// A shortest path from the start to `state`, both inclusive.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        self.path_from_index(*self.indices.get(state)?)
+    }
+
+    fn path_from_index(&self, mut index: usize) -> Option<Vec<S>> {
+        let mut path = vec![self.states[index].clone()];
+        while let Some(&previous) = self.predecessors[index].first() {
+            path.push(self.states[previous].clone());
+            index = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every state that lies on any shortest path from the start to any optimal goal.
+    pub fn on_optimal_paths(&self) -> Vec<&S> {
+        let mut seen = vec![false; self.states.len()];
+        let mut stack = self.goals.clone();
+        let mut result = vec![];
+
+        while let Some(index) = stack.pop() {
+            if std::mem::replace(&mut seen[index], true) {
+                continue;
+            }
+            result.push(&self.states[index]);
+            stack.extend(&self.predecessors[index]);
+        }
+        result
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Breadth-first search where every edge costs one.
+/// With `is_goal` always `false`, this computes the distance to every reachable state.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Paths<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut paths = Paths::new(start);
+    let mut queue = VecDeque::from([0]);
+    let mut best = None;
+
+    while let Some(index) = queue.pop_front() {
+        let cost = paths.costs[index];
+        if best.is_some_and(|best| cost > best) {
+            break;
+        }
+        if is_goal(&paths.states[index]) {
+            best = Some(cost);
+            paths.goals.push(index);
+            continue;
+        }
+
+        for next in successors(&paths.states[index]) {
+            if let Some(next) = paths.relax(index, next, cost + 1) {
+                queue.push_back(next);
+            }
+        }
+    }
+    paths
+}
+
+/// Dijkstra's algorithm for non-negative edge costs.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Paths<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    dijkstra_with(BinaryHeap::<Reverse<_>>::new(), start, successors, is_goal)
+}
+
+/// Dijkstra's algorithm using the given `queue`, e.g. a [`crate::queue::BucketQueue`].
+pub fn dijkstra_with<S, C, I>(
+    queue: impl Frontier<C, usize>,
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Paths<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar_with(queue, start, successors, |_| C::default(), is_goal)
+}
+
+/// A* search for non-negative edge costs.
+/// The `heuristic` has to be consistent, i.e. never overestimate the cost of a single edge.
+pub fn astar<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Paths<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar_with(
+        BinaryHeap::<Reverse<_>>::new(),
+        start,
+        successors,
+        heuristic,
+        is_goal,
+    )
+}
+
+/// A* search using the given `queue`.
+/// Monotone queues like [`crate::queue::BucketQueue`] need a heuristic that never lowers the
+/// priority along an edge.
+pub fn astar_with<S, C, I>(
+    mut queue: impl Frontier<C, usize>,
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Paths<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut paths = Paths::new(start);
+    let mut settled = vec![false];
+    let mut best = None;
+    queue.push(heuristic(&paths.states[0]), 0);
+
+    while let Some((priority, index)) = queue.pop() {
+        if best.is_some_and(|best| priority > best) {
+            break;
+        }
+        if std::mem::replace(&mut settled[index], true) {
+            continue;
+        }
+
+        let cost = paths.costs[index];
+        if is_goal(&paths.states[index]) {
+            best = Some(cost);
+            paths.goals.push(index);
+            continue;
+        }
+
+        for (next, step) in successors(&paths.states[index]) {
+            if let Some(next) = paths.relax(index, next, cost + step) {
+                settled.resize(paths.states.len(), false);
+                let priority = paths.costs[next] + heuristic(&paths.states[next]);
+                queue.push(priority, next);
+            }
+        }
+    }
+    paths
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{astar, bfs, dijkstra, dijkstra_with};
+    use crate::queue::{Queue, QueueKind};
+    use crate::{Dir4, Grid};
+
+    static MAZE: &str = "\
+S..#
+.#..
+...#
+#..E";
+
+    type Position = (usize, usize);
+
+    fn successors(grid: &Grid<char>, position: Position) -> impl Iterator<Item = Position> + '_ {
+        grid.neighbours4(position).filter(|next| grid[*next] != '#')
+    }
+
+    #[test]
+    fn finds_shortest_paths() {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let start = grid.find(&'S').unwrap();
+        let end = grid.find(&'E').unwrap();
+
+        let by_bfs = bfs(start, |p| successors(&grid, *p), |p| *p == end);
+        let by_dijkstra = dijkstra(
+            start,
+            |p| successors(&grid, *p).map(|n| (n, 1)),
+            |p| *p == end,
+        );
+        let by_astar = astar(
+            start,
+            |p| successors(&grid, *p).map(|n| (n, 1)),
+            |p| p.0.abs_diff(end.0) + p.1.abs_diff(end.1),
+            |p| *p == end,
+        );
+
+        assert_eq!(by_bfs.cost(), Some(6));
+        assert_eq!(by_dijkstra.cost(), Some(6));
+        assert_eq!(by_astar.cost(), Some(6));
+
+        let path = by_astar.path().unwrap();
+        assert_eq!(path.len(), 7);
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&end));
+        assert!(path
+            .windows(2)
+            .all(|w| w[0].0.abs_diff(w[1].0) + w[0].1.abs_diff(w[1].1) == 1));
+    }
+
+    #[test]
+    fn collects_all_optimal_predecessors() {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let start = grid.find(&'S').unwrap();
+        let end = grid.find(&'E').unwrap();
+        let paths = bfs(start, |p| successors(&grid, *p), |p| *p == end);
+
+        assert_eq!(paths.predecessors(&(2, 2)).count(), 2);
+        assert_eq!(paths.predecessors(&start).count(), 0);
+
+        let mut tiles = paths.on_optimal_paths();
+        tiles.sort();
+        assert_eq!(
+            tiles,
+            [
+                (0, 0),
+                (0, 1),
+                (0, 2),
+                (1, 0),
+                (1, 2),
+                (2, 0),
+                (2, 1),
+                (2, 2),
+                (3, 1),
+                (3, 2),
+                (3, 3)
+            ]
+            .iter()
+            .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn explores_everything_without_goal() {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let paths = bfs((0, 0), |p| successors(&grid, *p), |_| false);
+
+        assert_eq!(paths.cost(), None);
+        assert_eq!(paths.reached().count(), 12);
+        assert_eq!(paths.cost_to(&(1, 3)), Some(4));
+        assert_eq!(paths.cost_to(&(0, 3)), None);
+    }
+
+    #[test]
+    fn handles_weighted_states() {
+        // Moving forward costs 1, turning costs 1000.
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let start = (grid.find(&'S').unwrap(), Dir4::Right);
+        let end = grid.find(&'E').unwrap();
+
+        let paths = dijkstra(
+            start,
+            |&(position, direction)| {
+                let forward = grid
+                    .offset(position, direction.offset())
+                    .filter(|next| grid[*next] != '#')
+                    .map(|next| ((next, direction), 1));
+                forward.into_iter().chain([
+                    ((position, direction.turn_left()), 1000),
+                    ((position, direction.turn_right()), 1000),
+                ])
+            },
+            |&(position, _)| position == end,
+        );
+
+        assert_eq!(paths.cost(), Some(2006));
+        assert_eq!(paths.goals().collect::<Vec<_>>(), [&((3, 3), Dir4::Right)]);
+    }
+
+    #[test]
+    fn agrees_across_queues() {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let end = grid.find(&'E').unwrap();
+
+        for kind in [QueueKind::Heap, QueueKind::Bucket, QueueKind::ZeroOne] {
+            // every other step is free, so that all queues apply.
+            let paths = dijkstra_with(
+                Queue::new(kind),
+                (0, 0),
+                |p| successors(&grid, *p).map(|n| (n, (n.0 + n.1) % 2)),
+                |p| *p == end,
+            );
+            assert_eq!(paths.cost(), Some(3), "{kind}");
+            assert_eq!(paths.on_optimal_paths().len(), 11, "{kind}");
+        }
+    }
+}
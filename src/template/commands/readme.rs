@@ -0,0 +1,36 @@
+use std::process;
+
+use crate::template::export;
+use crate::template::readme::{self, Section};
+use crate::template::timings::Timings;
+use crate::template::{progress, readme_benchmarks};
+
+/// All sections that `cargo readme` regenerates. Adjust heading levels here.
+fn sections() -> Vec<Section> {
+    let timings = Timings::read_from_file();
+
+    // keep an exported benchmark chart up to date and embedded.
+    let chart = export::refresh_chart(&timings);
+
+    let statuses = progress::collect(&timings);
+
+    vec![
+        progress::section(statuses).heading_level(2),
+        readme_benchmarks::section(timings, chart).heading_level(2),
+    ]
+}
+
+pub fn handle() {
+    match readme::update_sections(&sections()) {
+        Ok(updated) if updated.is_empty() => {
+            println!("No readme sections found.");
+        }
+        Ok(updated) => {
+            println!("Updated readme sections: {}.", updated.join(", "));
+        }
+        Err(e) => {
+            eprintln!("Failed to update readme: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use crate::template::timings::Timings;
+use crate::template::{progress, readme};
+
+pub fn handle(store: bool) {
+    let statuses = progress::collect(&Timings::read_from_file());
+
+    for line in progress::format_terminal(&statuses) {
+        println!("{line}");
+    }
+
+    if store {
+        println!();
+        match readme::update_section(&progress::section(statuses)) {
+            Ok(()) => {
+                println!("Stored updated progress.");
+            }
+            Err(e) => {
+                eprintln!("Failed to store updated progress: {e}");
+            }
+        }
+    }
+}
@@ -1,20 +1,41 @@
 use std::collections::HashSet;
 
+use crate::queue::{QueueKind, QUEUE_ENV};
+use crate::template::environment::format_queue;
+use crate::template::export::{self, ExportFormat};
 use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::source_hash;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{all_days, readme_benchmarks, Day, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    compare: bool,
+    export: Option<ExportFormat>,
+    queue: Option<QueueKind>,
+) {
     let stored_timings = Timings::read_from_file();
 
+    // NOTE: solutions pick their search queue at runtime, the child processes inherit this variable.
+    if let Some(queue) = queue {
+        std::env::set_var(QUEUE_ENV, queue.to_string());
+        println!("Using the {queue} queue for searches.\n");
+    }
+
     let days_to_run = day.map_or_else(
         || {
             if run_all {
                 all_days().collect()
             } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
+                // when the `--all` flag is not set, filter out days that are fully benched
+                // and whose sources did not change since.
                 all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                    .filter(|day| {
+                        !stored_timings
+                            .is_day_up_to_date(*day, source_hash::for_day(*day).as_deref())
+                    })
                     .collect()
             }
         },
@@ -23,12 +44,28 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
 
     let timings = run_multi(&days_to_run, true, true).unwrap();
 
+    if compare {
+        print_comparison(&stored_timings, &timings);
+    }
+
+    let merged_timings = stored_timings.merge(&timings);
+
+    if let Some(format) = export {
+        println!();
+        match export::write(format, &merged_timings) {
+            Ok(path) => println!("Exported benchmarks to \"{path}\"."),
+            Err(e) => eprintln!("Failed to export benchmarks: {e}"),
+        }
+    }
+
     if store {
-        let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
+        // keep an exported benchmark chart up to date and embedded, like `cargo readme` does.
+        let chart = export::refresh_chart(&merged_timings);
+
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(merged_timings, chart) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -38,3 +75,59 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         }
     }
 }
+
+/// Print the change in total time for every benched day that has a stored timing.
+/// Warns if the stored timing was recorded in a different environment.
+fn print_comparison(stored_timings: &Timings, timings: &Timings) {
+    println!("\n{ANSI_BOLD}Comparison with stored timings:{ANSI_RESET}");
+
+    for timing in &timings.data {
+        let Some(stored) = stored_timings.get(timing.day) else {
+            println!("Day {}: no stored timing.", timing.day);
+            continue;
+        };
+
+        let old_millis = stored.total_nanos / 1_000_000_f64;
+        let new_millis = timing.total_nanos / 1_000_000_f64;
+        let change = if stored.total_nanos > 0_f64 {
+            format!(
+                " ({:+.1}%)",
+                (timing.total_nanos - stored.total_nanos) / stored.total_nanos * 100_f64
+            )
+        } else {
+            String::new()
+        };
+
+        println!(
+            "Day {}: {old_millis:.2}ms → {new_millis:.2}ms{change}",
+            timing.day
+        );
+
+        // NOTE: comparing queues against each other is intended, so this is a note and no warning.
+        let queue = |timing: &Timing| timing.environment.as_ref().and_then(|e| e.queue);
+        let (old_queue, new_queue) = (queue(stored), queue(timing));
+        if old_queue != new_queue {
+            println!(
+                "  queue: {} → {}",
+                format_queue(old_queue),
+                format_queue(new_queue)
+            );
+        }
+
+        match (&stored.environment, &timing.environment) {
+            (Some(old), Some(new)) => {
+                let differences = old.diff(new);
+                if !differences.is_empty() {
+                    eprintln!(
+                        "  ⚠ environment differs from stored timing: {}",
+                        differences.join("; ")
+                    );
+                }
+            }
+            (None, Some(_)) => {
+                eprintln!("  ⚠ stored timing has no recorded environment.");
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,355 @@
+/// Describes the machine and toolchain a set of benchmarks was recorded with.
+use std::{collections::HashMap, fmt::Display, thread};
+use tinyjson::JsonValue;
+
+use crate::queue::QueueKind;
+use crate::template::runner::{BenchSettings, BENCH_SETTINGS};
+
+/// Target features that are reported if they were enabled at compile time.
+const KNOWN_TARGET_FEATURES: [(&str, bool); 12] = [
+    ("sse2", cfg!(target_feature = "sse2")),
+    ("sse4.1", cfg!(target_feature = "sse4.1")),
+    ("sse4.2", cfg!(target_feature = "sse4.2")),
+    ("popcnt", cfg!(target_feature = "popcnt")),
+    ("avx", cfg!(target_feature = "avx")),
+    ("avx2", cfg!(target_feature = "avx2")),
+    ("fma", cfg!(target_feature = "fma")),
+    ("bmi2", cfg!(target_feature = "bmi2")),
+    ("avx512f", cfg!(target_feature = "avx512f")),
+    ("neon", cfg!(target_feature = "neon")),
+    ("aes", cfg!(target_feature = "aes")),
+    ("sve", cfg!(target_feature = "sve")),
+];
+
+/// Benchmark environment stored alongside a [`super::timings::Timing`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Environment {
+    pub cpu_model: String,
+    pub cores: usize,
+    /// The compiler that built the solutions, recorded by `build.rs`.
+    pub rustc_version: String,
+    pub profile: String,
+    pub target: String,
+    pub target_features: Vec<String>,
+    pub bench: BenchSettings,
+    /// The queue forced with `cargo time --queue`, [`None`] if every solution used its default.
+    pub queue: Option<QueueKind>,
+}
+
+impl Environment {
+    /// Collects information about the current machine.
+    pub fn detect(is_release: bool) -> Self {
+        Environment {
+            cpu_model: cpu_model().unwrap_or_else(|| "unknown CPU".into()),
+            cores: thread::available_parallelism().map_or(1, std::num::NonZero::get),
+            rustc_version: option_env!("AOC_RUSTC_VERSION")
+                .unwrap_or("unknown rustc")
+                .into(),
+            profile: if is_release { "release" } else { "dev" }.into(),
+            target: format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS),
+            target_features: KNOWN_TARGET_FEATURES
+                .iter()
+                .filter(|(_, enabled)| *enabled)
+                .map(|(name, _)| (*name).to_string())
+                .collect(),
+            bench: BENCH_SETTINGS,
+            queue: QueueKind::from_env().ok().flatten(),
+        }
+    }
+
+    /// Lists human-readable descriptions of all fields that differ between `self` and `other`.
+    pub fn diff(&self, other: &Self) -> Vec<String> {
+        let mut differences = vec![];
+
+        let mut compare = |name: &str, a: String, b: String| {
+            if a != b {
+                differences.push(format!("{name}: `{a}` → `{b}`"));
+            }
+        };
+
+        compare("CPU", self.cpu_model.clone(), other.cpu_model.clone());
+        compare("cores", self.cores.to_string(), other.cores.to_string());
+        compare(
+            "rustc",
+            self.rustc_version.clone(),
+            other.rustc_version.clone(),
+        );
+        compare("profile", self.profile.clone(), other.profile.clone());
+        compare("target", self.target.clone(), other.target.clone());
+        compare(
+            "target features",
+            self.target_features.join(", "),
+            other.target_features.join(", "),
+        );
+        compare(
+            "bench settings",
+            format_bench_settings(&self.bench),
+            format_bench_settings(&other.bench),
+        );
+        // NOTE: the queue is left out on purpose, `cargo time --compare` reports it on its own.
+
+        differences
+    }
+}
+
+fn format_bench_settings(bench: &BenchSettings) -> String {
+    format!(
+        "{}-{} samples, {:?} target",
+        bench.min_samples, bench.max_samples, bench.target
+    )
+}
+
+/// The queue kind, or `default` if every solution used its own.
+pub fn format_queue(queue: Option<QueueKind>) -> String {
+    queue.map_or_else(|| "default".into(), |queue| queue.to_string())
+}
+
+impl Display for Environment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let features = if self.target_features.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", self.target_features.join(", "))
+        };
+        let queue = self
+            .queue
+            .map_or_else(String::new, |queue| format!(", {queue} queue"));
+
+        write!(
+            f,
+            "{} ({} cores), {}, `{}` profile, {}{}, {}{}",
+            self.cpu_model,
+            self.cores,
+            self.rustc_version,
+            self.profile,
+            self.target,
+            features,
+            format_bench_settings(&self.bench),
+            queue
+        )
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn cpu_model() -> Option<String> {
+    let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").ok()?;
+    cpuinfo
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(key, _)| matches!(key.trim(), "model name" | "Model" | "Hardware"))
+        .map(|(_, value)| value.trim().to_string())
+}
+
+#[cfg(target_os = "macos")]
+fn cpu_model() -> Option<String> {
+    command_output("sysctl", &["-n", "machdep.cpu.brand_string"])
+}
+
+#[cfg(target_os = "windows")]
+fn cpu_model() -> Option<String> {
+    std::env::var("PROCESSOR_IDENTIFIER").ok()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn cpu_model() -> Option<String> {
+    None
+}
+
+#[cfg(target_os = "macos")]
+fn command_output(cmd: &str, args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new(cmd).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!stdout.is_empty()).then_some(stdout)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Environment> for JsonValue {
+    fn from(value: &Environment) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "cpu_model".into(),
+            JsonValue::String(value.cpu_model.clone()),
+        );
+        map.insert("cores".into(), JsonValue::Number(value.cores as f64));
+        map.insert(
+            "rustc_version".into(),
+            JsonValue::String(value.rustc_version.clone()),
+        );
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert("target".into(), JsonValue::String(value.target.clone()));
+        map.insert(
+            "target_features".into(),
+            JsonValue::Array(
+                value
+                    .target_features
+                    .iter()
+                    .cloned()
+                    .map(JsonValue::String)
+                    .collect(),
+            ),
+        );
+
+        let mut bench: HashMap<String, JsonValue> = HashMap::new();
+        bench.insert(
+            "min_samples".into(),
+            JsonValue::Number(value.bench.min_samples as f64),
+        );
+        bench.insert(
+            "max_samples".into(),
+            JsonValue::Number(value.bench.max_samples as f64),
+        );
+        bench.insert(
+            "target_nanos".into(),
+            JsonValue::Number(value.bench.target.as_nanos() as f64),
+        );
+        map.insert("bench".into(), JsonValue::Object(bench));
+
+        if let Some(queue) = value.queue {
+            map.insert("queue".into(), JsonValue::String(queue.to_string()));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Environment {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected environment to be a JSON object.")?;
+
+        let get_string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected environment.{key} to be a string."))
+        };
+
+        let cores = json
+            .get("cores")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected environment.cores to be a number.")?;
+
+        let target_features = json
+            .get("target_features")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .and_then(|features| {
+                features
+                    .iter()
+                    .map(|f| f.get::<String>().cloned())
+                    .collect::<Option<Vec<_>>>()
+            })
+            .ok_or("Expected environment.target_features to be an array of strings.")?;
+
+        let bench = json
+            .get("bench")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("Expected environment.bench to be a JSON object.")?;
+
+        let get_bench_number = |key: &str| {
+            bench
+                .get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected environment.bench.{key} to be a number."))
+        };
+
+        // NOTE: timings stored before the queue was recorded used the default queues.
+        let queue = match json.get("queue") {
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected environment.queue to be a string.")?
+                    .parse::<QueueKind>()
+                    .map_err(|e| e.to_string())?,
+            ),
+            None => None,
+        };
+
+        Ok(Environment {
+            cpu_model: get_string("cpu_model")?,
+            cores: cores as usize,
+            rustc_version: get_string("rustc_version")?,
+            profile: get_string("profile")?,
+            target: get_string("target")?,
+            target_features,
+            bench: BenchSettings {
+                min_samples: get_bench_number("min_samples")? as u128,
+                max_samples: get_bench_number("max_samples")? as u128,
+                target: std::time::Duration::from_nanos(get_bench_number("target_nanos")? as u64),
+            },
+            queue,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+pub(crate) mod tests {
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    use super::Environment;
+    use crate::queue::QueueKind;
+    use crate::template::runner::BenchSettings;
+
+    pub fn get_mock_environment() -> Environment {
+        Environment {
+            cpu_model: "Mock CPU 9000".into(),
+            cores: 8,
+            rustc_version: "rustc 1.83.0".into(),
+            profile: "release".into(),
+            target: "x86_64-linux".into(),
+            target_features: vec!["sse2".into(), "avx2".into()],
+            bench: BenchSettings {
+                min_samples: 10,
+                max_samples: 10000,
+                target: Duration::from_secs(1),
+            },
+            queue: None,
+        }
+    }
+
+    #[test]
+    fn roundtrips_through_json() {
+        let mut environment = get_mock_environment();
+        let json = JsonValue::from(&environment);
+        assert_eq!(Environment::try_from(&json).unwrap(), environment);
+
+        environment.queue = Some(QueueKind::Bucket);
+        let json = JsonValue::from(&environment);
+        assert_eq!(Environment::try_from(&json).unwrap(), environment);
+    }
+
+    #[test]
+    fn diffs_changed_fields() {
+        let environment = get_mock_environment();
+        let mut other = get_mock_environment();
+        other.rustc_version = "rustc 1.84.0".into();
+        other.cores = 16;
+
+        let diff = environment.diff(&other);
+        assert_eq!(diff.len(), 2);
+        assert!(diff[0].starts_with("cores"));
+        assert!(diff[1].starts_with("rustc"));
+        assert!(environment.diff(&get_mock_environment()).is_empty());
+
+        other = get_mock_environment();
+        other.queue = Some(QueueKind::ZeroOne);
+        assert!(environment.diff(&other).is_empty());
+    }
+
+    #[test]
+    fn formats_for_display() {
+        assert_eq!(
+            get_mock_environment().to_string(),
+            "Mock CPU 9000 (8 cores), rustc 1.83.0, `release` profile, x86_64-linux (sse2, avx2), 10-10000 samples, 1s target"
+        );
+    }
+}
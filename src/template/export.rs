@@ -0,0 +1,371 @@
+/// Module that exports timings to formats other than the readme table.
+use std::{collections::HashMap, error::Error, fmt::Display, fs, io, path::Path, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::run_multi::child_commands::parse_duration;
+use crate::template::timings::{Timing, Timings};
+
+const CHART_WIDTH: f64 = 600.0;
+const CHART_MARGIN_LEFT: f64 = 70.0;
+const CHART_MARGIN_RIGHT: f64 = 30.0;
+const CHART_MARGIN_TOP: f64 = 50.0;
+const CHART_MARGIN_BOTTOM: f64 = 30.0;
+const CHART_ROW_HEIGHT: f64 = 26.0;
+const CHART_BAR_HEIGHT: f64 = 9.0;
+const CHART_COLORS: [&str; 2] = ["#4e79a7", "#f28e2b"];
+
+/// Formats that `cargo time --export <format>` supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    JsonLines,
+    Svg,
+}
+
+impl ExportFormat {
+    /// Path the export is written to.
+    pub fn path(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "./data/benchmarks.csv",
+            ExportFormat::JsonLines => "./data/benchmarks.jsonl",
+            ExportFormat::Svg => "./data/benchmarks.svg",
+        }
+    }
+
+    /// Render timings in this format.
+    pub fn render(self, timings: &Timings) -> String {
+        match self {
+            ExportFormat::Csv => to_csv(timings),
+            ExportFormat::JsonLines => to_json_lines(timings),
+            ExportFormat::Svg => to_svg(timings),
+        }
+    }
+}
+
+/// Render timings and write them to the export path of `format`.
+pub fn write(format: ExportFormat, timings: &Timings) -> Result<&'static str, io::Error> {
+    let path = format.path();
+    fs::write(path, format.render(timings))?;
+    Ok(path)
+}
+
+/// Re-renders the benchmark chart if it was exported before.
+/// Returns its path if it is up to date, so the readme can embed it.
+pub fn refresh_chart(timings: &Timings) -> Option<&'static str> {
+    let path = ExportFormat::Svg.path();
+    if !Path::new(path).exists() {
+        return None;
+    }
+    write(ExportFormat::Svg, timings).ok()
+}
+
+impl FromStr for ExportFormat {
+    type Err = ExportFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "jsonl" | "ndjson" | "json-lines" => Ok(ExportFormat::JsonLines),
+            "svg" => Ok(ExportFormat::Svg),
+            _ => Err(ExportFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`ExportFormat`].
+#[derive(Debug)]
+pub struct ExportFormatFromStrError;
+
+impl Error for ExportFormatFromStrError {}
+
+impl Display for ExportFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `csv`, `jsonl` or `svg` as export format")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn part_nanos(part: Option<&String>) -> Option<f64> {
+    part.and_then(|s| parse_duration(s))
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn to_csv(timings: &Timings) -> String {
+    let mut lines = vec![
+        "day,part_1,part_1_nanos,part_2,part_2_nanos,total_nanos,cpu_model,rustc_version"
+            .to_string(),
+    ];
+
+    for timing in &timings.data {
+        let optional = |s: Option<String>| s.unwrap_or_default();
+        lines.push(
+            [
+                timing.day.to_string(),
+                optional(timing.part_1.clone()),
+                optional(part_nanos(timing.part_1.as_ref()).map(|n| n.to_string())),
+                optional(timing.part_2.clone()),
+                optional(part_nanos(timing.part_2.as_ref()).map(|n| n.to_string())),
+                timing.total_nanos.to_string(),
+                optional(timing.environment.as_ref().map(|e| e.cpu_model.clone())),
+                optional(timing.environment.as_ref().map(|e| e.rustc_version.clone())),
+            ]
+            .iter()
+            .map(|field| csv_field(field))
+            .collect::<Vec<_>>()
+            .join(","),
+        );
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn to_json_lines(timings: &Timings) -> String {
+    timings
+        .data
+        .iter()
+        .map(|timing| {
+            let mut json = JsonValue::from(timing);
+            if let Some(map) = json.get_mut::<HashMap<String, JsonValue>>() {
+                for (key, part) in [
+                    ("part_1_nanos", &timing.part_1),
+                    ("part_2_nanos", &timing.part_2),
+                ] {
+                    let value =
+                        part_nanos(part.as_ref()).map_or(JsonValue::Null, JsonValue::Number);
+                    map.insert(key.into(), value);
+                }
+            }
+            // NOTE: stringifying only fails for non-finite numbers, which timings never contain.
+            json.stringify().unwrap_or_default() + "\n"
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn format_decade(exponent: i32) -> String {
+    let units = ["ns", "µs", "ms", "s"];
+    let unit_index = usize::try_from(exponent.clamp(0, 9) / 3).unwrap_or(0);
+    let value = 10_f64.powi(exponent - 3 * i32::try_from(unit_index).unwrap_or(0));
+    format!("{value}{}", units[unit_index])
+}
+
+/// Render a bar chart of per-part times on a logarithmic scale.
+fn to_svg(timings: &Timings) -> String {
+    let bars: Vec<(&Timing, [Option<f64>; 2])> = timings
+        .data
+        .iter()
+        .map(|t| {
+            (
+                t,
+                [part_nanos(t.part_1.as_ref()), part_nanos(t.part_2.as_ref())],
+            )
+        })
+        .collect();
+
+    let all_nanos: Vec<f64> = bars
+        .iter()
+        .flat_map(|(_, parts)| parts.iter().flatten())
+        .map(|n| n.max(1.0))
+        .collect();
+    let height =
+        CHART_MARGIN_TOP + CHART_ROW_HEIGHT * bars.len().max(1) as f64 + CHART_MARGIN_BOTTOM;
+    let width = CHART_MARGIN_LEFT + CHART_WIDTH + CHART_MARGIN_RIGHT;
+
+    let mut svg = vec![
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif" font-size="12">"#
+        ),
+        format!(r#"<rect width="{width}" height="{height}" fill="white"/>"#),
+        format!(
+            r#"<text x="{CHART_MARGIN_LEFT}" y="18" font-size="14" font-weight="bold">Benchmarks (log scale, total: {:.2}ms)</text>"#,
+            timings.total_millis()
+        ),
+    ];
+
+    for (i, color) in CHART_COLORS.iter().enumerate() {
+        let x = CHART_MARGIN_LEFT + 80.0 * i as f64;
+        svg.push(format!(
+            r#"<rect x="{x}" y="28" width="10" height="10" fill="{color}"/><text x="{}" y="37">Part {}</text>"#,
+            x + 14.0,
+            i + 1
+        ));
+    }
+
+    if all_nanos.is_empty() {
+        svg.push(format!(
+            r#"<text x="{CHART_MARGIN_LEFT}" y="{}">No benchmarks stored.</text>"#,
+            CHART_MARGIN_TOP + 16.0
+        ));
+        svg.push("</svg>".into());
+        return svg.join("\n") + "\n";
+    }
+    let min_exponent = all_nanos
+        .iter()
+        .map(|n| n.log10().floor() as i32)
+        .min()
+        .unwrap_or(0);
+    let max_exponent = all_nanos
+        .iter()
+        .map(|n| n.log10().ceil() as i32)
+        .max()
+        .unwrap_or(0)
+        .max(min_exponent + 1);
+
+    let scale = |nanos: f64| {
+        (nanos.max(1.0).log10() - f64::from(min_exponent)) / f64::from(max_exponent - min_exponent)
+            * CHART_WIDTH
+    };
+
+    let axis_y = height - CHART_MARGIN_BOTTOM;
+
+    for exponent in min_exponent..=max_exponent {
+        let x = CHART_MARGIN_LEFT + scale(10_f64.powi(exponent));
+        svg.push(format!(
+            r##"<line x1="{x}" y1="{CHART_MARGIN_TOP}" x2="{x}" y2="{axis_y}" stroke="#ddd"/><text x="{x}" y="{}" text-anchor="middle" fill="#555">{}</text>"##,
+            axis_y + 16.0,
+            format_decade(exponent)
+        ));
+    }
+
+    for (row, (timing, parts)) in bars.iter().enumerate() {
+        let y = CHART_MARGIN_TOP + CHART_ROW_HEIGHT * row as f64 + 3.0;
+
+        svg.push(format!(
+            r#"<text x="{}" y="{}" text-anchor="end">Day {}</text>"#,
+            CHART_MARGIN_LEFT - 8.0,
+            y + CHART_BAR_HEIGHT + 4.0,
+            timing.day
+        ));
+
+        for (i, (nanos, label)) in parts
+            .iter()
+            .zip([&timing.part_1, &timing.part_2])
+            .enumerate()
+        {
+            let Some(nanos) = nanos else {
+                continue;
+            };
+            let bar_y = y + (CHART_BAR_HEIGHT + 2.0) * i as f64;
+            svg.push(format!(
+                r#"<rect x="{CHART_MARGIN_LEFT}" y="{bar_y}" width="{:.1}" height="{CHART_BAR_HEIGHT}" fill="{}"><title>Day {} part {}: {}</title></rect>"#,
+                scale(*nanos).max(1.0),
+                CHART_COLORS[i],
+                timing.day,
+                i + 1,
+                escape_xml(label.as_deref().unwrap_or("-"))
+            ));
+        }
+    }
+
+    svg.push(format!(
+        r##"<line x1="{CHART_MARGIN_LEFT}" y1="{CHART_MARGIN_TOP}" x2="{CHART_MARGIN_LEFT}" y2="{axis_y}" stroke="#333"/>"##
+    ));
+    svg.push("</svg>".into());
+    svg.join("\n") + "\n"
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_decade, ExportFormat};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some("10ms".into()),
+                    part_2: Some("20µs".into()),
+                    total_nanos: 10_020_000.0,
+                    environment: None,
+                    source_hash: None,
+                },
+                Timing {
+                    day: day!(2),
+                    part_1: Some("30ns".into()),
+                    part_2: None,
+                    total_nanos: 30.0,
+                    environment: None,
+                    source_hash: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("csv".parse::<ExportFormat>().unwrap(), ExportFormat::Csv);
+        assert_eq!(
+            "jsonl".parse::<ExportFormat>().unwrap(),
+            ExportFormat::JsonLines
+        );
+        assert_eq!("SVG".parse::<ExportFormat>().unwrap(), ExportFormat::Svg);
+        assert!("xml".parse::<ExportFormat>().is_err());
+    }
+
+    #[test]
+    fn exports_csv() {
+        let csv = ExportFormat::Csv.render(&get_mock_timings());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1], "01,10ms,10000000,20µs,20000,10020000,,");
+        assert_eq!(lines[2], "02,30ns,30,,,30,,");
+    }
+
+    #[test]
+    fn exports_json_lines() {
+        let jsonl = ExportFormat::JsonLines.render(&get_mock_timings());
+        let lines: Vec<&str> = jsonl.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let parsed: tinyjson::JsonValue = lines[1].parse().unwrap();
+        assert_eq!(parsed["part_1_nanos"], tinyjson::JsonValue::Number(30.0));
+        assert!(parsed["part_2_nanos"].is_null());
+    }
+
+    #[test]
+    fn exports_svg() {
+        let svg = ExportFormat::Svg.render(&get_mock_timings());
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<title>").count(), 3);
+        assert!(svg.contains(">10ns<"));
+        assert!(svg.contains(">10ms<"));
+        assert!(!svg.contains(">100ms<"));
+    }
+
+    #[test]
+    fn exports_empty_svg() {
+        let svg = ExportFormat::Svg.render(&Timings::default());
+        assert!(svg.contains("No benchmarks stored."));
+    }
+
+    #[test]
+    fn formats_decades() {
+        assert_eq!(format_decade(0), "1ns");
+        assert_eq!(format_decade(4), "10µs");
+        assert_eq!(format_decade(8), "100ms");
+        assert_eq!(format_decade(10), "10s");
+    }
+}
@@ -2,13 +2,18 @@ use std::{env, fs};
 
 pub mod aoc_cli;
 pub mod commands;
+pub mod export;
 pub mod runner;
 
 pub use day::*;
 
 mod day;
+mod environment;
+mod progress;
+mod readme;
 mod readme_benchmarks;
 mod run_multi;
+mod source_hash;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
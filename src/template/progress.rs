@@ -0,0 +1,406 @@
+/// Module that derives puzzle progress from local files.
+/// This is an offline alternative to the `readme-stars` workflow: a part counts as locked
+/// once the puzzle description downloaded by `aoc-cli` contains its accepted answer.
+use std::fs;
+
+use crate::template::readme::Section;
+use crate::template::readme_benchmarks::get_path_for_bin;
+use crate::template::timings::Timings;
+use crate::template::{all_days, Day, ANSI_BOLD, ANSI_RESET};
+
+static MARKER: &str = "<!--- progress table --->";
+static ANSWER_PREFIX: &str = "Your puzzle answer was";
+
+/// Progress of a single part of a puzzle.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PartStatus {
+    /// The solution function does something other than returning `None`.
+    pub implemented: bool,
+    /// The accepted answer is present in the downloaded puzzle description.
+    pub locked: bool,
+    /// The stored benchmark time, if any.
+    pub benchmark: Option<String>,
+}
+
+/// Progress of a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayStatus {
+    pub day: Day,
+    pub has_bin: bool,
+    pub parts: [PartStatus; 2],
+}
+
+impl DayStatus {
+    fn stars(&self) -> usize {
+        self.parts.iter().filter(|p| p.locked).count()
+    }
+}
+
+/// Collect the status of every day that has a solution bin.
+pub fn collect(timings: &Timings) -> Vec<DayStatus> {
+    all_days()
+        .map(|day| {
+            let source = fs::read_to_string(get_path_for_bin(day)).ok();
+            let puzzle = fs::read_to_string(get_puzzle_path(day)).unwrap_or_default();
+            day_status(day, source.as_deref(), &puzzle, timings)
+        })
+        .filter(|status| status.has_bin)
+        .collect()
+}
+
+fn get_puzzle_path(day: Day) -> String {
+    format!("data/puzzles/{day}.md")
+}
+
+fn day_status(day: Day, source: Option<&str>, puzzle: &str, timings: &Timings) -> DayStatus {
+    let answers = puzzle.matches(ANSWER_PREFIX).count();
+    let timing = timings.get(day);
+
+    let part = |name: &str, index: usize| PartStatus {
+        implemented: source.is_some_and(|s| is_implemented(s, name)),
+        locked: answers > index,
+        benchmark: timing.and_then(|t| {
+            if index == 0 {
+                t.part_1.clone()
+            } else {
+                t.part_2.clone()
+            }
+        }),
+    };
+
+    DayStatus {
+        day,
+        has_bin: source.is_some(),
+        parts: [part("part_one", 0), part("part_two", 1)],
+    }
+}
+
+/// Checks if the function `name` exists and is not a stub, see [`is_stub`].
+fn is_implemented(source: &str, name: &str) -> bool {
+    let source = strip_comments_and_literals(source);
+    let signature = format!("fn {name}");
+    let Some(start) = source.match_indices(&signature).map(|(i, _)| i).find(|&i| {
+        source[i + signature.len()..]
+            .trim_start()
+            .starts_with(['(', '<'])
+    }) else {
+        return false;
+    };
+    let Some(open) = source[start..].find('{').map(|i| start + i) else {
+        return false;
+    };
+
+    let mut depth = 0;
+    let mut close = None;
+    for (i, c) in source[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(open + i);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let Some(close) = close else {
+        return false;
+    };
+
+    !is_stub(source[open + 1..close].trim())
+}
+
+/// A stub's final expression is `None` and every early `return` returns `None` as well,
+/// e.g. `println!("{input}"); None`.
+fn is_stub(body: &str) -> bool {
+    let Some(rest) = body.strip_suffix("None") else {
+        return false;
+    };
+    let rest = rest.trim_end();
+    let is_final_expression = rest.is_empty() || rest.ends_with(';') || rest.ends_with('}');
+    let returns_none = body
+        .match_indices("return")
+        .all(|(i, _)| body[i + "return".len()..].trim_start().starts_with("None"));
+    is_final_expression && returns_none
+}
+
+/// `source` without comments and with empty string and char literals, so that braces and
+/// keywords inside them are not mistaken for code.
+fn strip_comments_and_literals(source: &str) -> String {
+    let chars: Vec<char> = source.chars().collect();
+    let is_ident = |i: usize| chars[i].is_alphanumeric() || chars[i] == '_';
+    let mut stripped = String::with_capacity(source.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        match (chars[i], chars.get(i + 1).copied()) {
+            ('/', Some('/')) => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            ('/', Some('*')) => {
+                // NOTE: block comments nest.
+                let mut depth = 0;
+                while i < chars.len() {
+                    match (chars[i], chars.get(i + 1).copied()) {
+                        ('/', Some('*')) => (depth, i) = (depth + 1, i + 2),
+                        ('*', Some('/')) => (depth, i) = (depth - 1, i + 2),
+                        _ => i += 1,
+                    }
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                stripped.push(' ');
+            }
+            // raw strings like `r#"..."#`, also with a `b` prefix.
+            ('r', Some('"' | '#'))
+                if i == 0
+                    || !is_ident(i - 1)
+                    || (chars[i - 1] == 'b' && (i == 1 || !is_ident(i - 2))) =>
+            {
+                let hashes = chars[i + 1..].iter().take_while(|&&c| c == '#').count();
+                if chars.get(i + 1 + hashes) != Some(&'"') {
+                    // a raw identifier like `r#type`.
+                    stripped.push('r');
+                    i += 1;
+                    continue;
+                }
+                i += hashes + 2;
+                while i < chars.len()
+                    && !(chars[i] == '"'
+                        && chars[i + 1..].iter().take_while(|&&c| c == '#').count() >= hashes)
+                {
+                    i += 1;
+                }
+                i += hashes + 1;
+                stripped.push_str("\"\"");
+            }
+            ('"', _) => {
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    i += if chars[i] == '\\' { 2 } else { 1 };
+                }
+                i += 1;
+                stripped.push_str("\"\"");
+            }
+            // a char literal like `'{'` or `'\''`, but not a lifetime like `'a`.
+            ('\'', Some('\\')) => {
+                i += 2;
+                while i < chars.len() && chars[i] != '\'' {
+                    i += 1;
+                }
+                i += 1;
+                stripped.push_str("' '");
+            }
+            ('\'', Some(_)) if chars.get(i + 2) == Some(&'\'') => {
+                i += 3;
+                stripped.push_str("' '");
+            }
+            (c, _) => {
+                stripped.push(c);
+                i += 1;
+            }
+        }
+    }
+    stripped
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn format_part(part: &PartStatus) -> &'static str {
+    match part {
+        PartStatus { locked: true, .. } => "⭐",
+        PartStatus {
+            implemented: true, ..
+        } => "✔",
+        _ => "✖",
+    }
+}
+
+/// Render progress for the command-line.
+pub fn format_terminal(statuses: &[DayStatus]) -> Vec<String> {
+    let mut lines = vec![];
+
+    for status in statuses {
+        let parts: Vec<String> = status
+            .parts
+            .iter()
+            .enumerate()
+            .map(|(i, part)| {
+                let benchmark = part
+                    .benchmark
+                    .as_ref()
+                    .map(|b| format!(" ({b})"))
+                    .unwrap_or_default();
+                format!("Part {}: {}{benchmark}", i + 1, format_part(part))
+            })
+            .collect();
+
+        lines.push(format!(
+            "{ANSI_BOLD}Day {}{ANSI_RESET}  {}",
+            status.day,
+            parts.join("  ")
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format_summary(statuses));
+    lines
+}
+
+fn format_summary(statuses: &[DayStatus]) -> String {
+    let stars: usize = statuses.iter().map(DayStatus::stars).sum();
+    let implemented: usize = statuses
+        .iter()
+        .map(|s| s.parts.iter().filter(|p| p.implemented).count())
+        .sum();
+    let benchmarked: usize = statuses
+        .iter()
+        .map(|s| s.parts.iter().filter(|p| p.benchmark.is_some()).count())
+        .sum();
+
+    format!(
+        "Days: {}/25, stars: {stars}/50, implemented: {implemented}/50, benchmarked: {benchmarked}/50",
+        statuses.len()
+    )
+}
+
+fn construct_table(statuses: &[DayStatus]) -> Vec<String> {
+    let mut lines: Vec<String> = vec![
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for status in statuses {
+        lines.push(format!(
+            "| [Day {}]({}) | {} | {} |",
+            status.day.into_inner(),
+            get_path_for_bin(status.day),
+            format_part(&status.parts[0]),
+            format_part(&status.parts[1])
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**{}**", format_summary(statuses)));
+    lines
+}
+
+/// The progress section of the readme.
+pub fn section(statuses: Vec<DayStatus>) -> Section {
+    Section::new("progress", MARKER, "Progress", move || {
+        Ok(construct_table(&statuses))
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{construct_table, day_status, is_implemented};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+
+    static SOURCE: &str = r#"
+pub fn part_one(input: &str) -> Option<u32> {
+    let x = { 1 };
+    Some(x)
+}
+
+pub fn part_two(_input: &str) -> Option<u32> {
+    // TODO
+    None
+}
+"#;
+
+    #[test]
+    fn detects_stubs() {
+        assert!(is_implemented(SOURCE, "part_one"));
+        assert!(!is_implemented(SOURCE, "part_two"));
+        assert!(!is_implemented(SOURCE, "part_three"));
+    }
+
+    #[test]
+    fn detects_multi_statement_stubs() {
+        let source = r##"
+pub fn part_one(input: &str) -> Option<u32> {
+    let map = parse(input);
+    println!("{map:?} }}");
+    /* still { to do */
+    None
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    if input.is_empty() {
+        return None;
+    }
+    let brace = '}';
+    let raw = r#"" } "#;
+    println!("{brace}{raw}");
+    None // TODO
+}
+
+pub fn part_three<'a>(input: &'a str) -> Option<&'a str> {
+    if input.contains('{') {
+        return Some(input);
+    }
+    None
+}
+"##;
+        assert!(!is_implemented(source, "part_one"));
+        assert!(!is_implemented(source, "part_two"));
+        assert!(is_implemented(source, "part_three"));
+    }
+
+    #[test]
+    fn derives_day_status() {
+        let timings = Timings {
+            data: vec![Timing {
+                day: day!(3),
+                part_1: Some("1ms".into()),
+                part_2: None,
+                total_nanos: 1_000_000.0,
+                environment: None,
+                source_hash: None,
+            }],
+        };
+        let puzzle = "--- Part One ---\nYour puzzle answer was `42`.\n--- Part Two ---";
+        let status = day_status(day!(3), Some(SOURCE), puzzle, &timings);
+
+        assert!(status.has_bin);
+        assert!(status.parts[0].implemented);
+        assert!(status.parts[0].locked);
+        assert_eq!(status.parts[0].benchmark, Some("1ms".into()));
+        assert!(!status.parts[1].implemented);
+        assert!(!status.parts[1].locked);
+        assert_eq!(status.parts[1].benchmark, None);
+    }
+
+    #[test]
+    fn handles_missing_bins() {
+        let status = day_status(day!(4), None, "", &Timings::default());
+        assert!(!status.has_bin);
+        assert!(!status.parts[0].implemented);
+    }
+
+    #[test]
+    fn formats_table() {
+        let status = day_status(
+            day!(3),
+            Some(SOURCE),
+            "Your puzzle answer was `42`.",
+            &Timings::default(),
+        );
+        let lines = construct_table(&[status]);
+        assert_eq!(lines[2], "| [Day 3](./src/bin/03.rs) | ⭐ | ✖ |");
+        assert_eq!(
+            lines[4],
+            "**Days: 1/25, stars: 1/50, implemented: 1/50, benchmarked: 0/50**"
+        );
+    }
+}
@@ -0,0 +1,214 @@
+/// Module that regenerates marker-delimited sections of the readme.
+/// The approach taken is similar to how `aoc-readme-stars` handles this:
+/// every section lives between two identical `<!--- name --->` markers and is replaced as a whole.
+use std::{fmt::Display, fs, io};
+
+static README_PATH: &str = "README.md";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Renders the body of a section, i.e. everything below its heading.
+pub type Renderer = Box<dyn Fn() -> Result<Vec<String>, Error>>;
+
+/// A readme section that is delimited by a pair of markers.
+pub struct Section {
+    /// Short name used in command-line output, e.g. `benchmarks`.
+    pub name: &'static str,
+    /// The marker comment, e.g. `<!--- benchmarking table --->`.
+    pub marker: String,
+    /// Markdown heading level of the title. `0` omits the heading.
+    pub heading_level: usize,
+    pub title: String,
+    pub render: Renderer,
+}
+
+impl Section {
+    /// Creates a section with a level two heading.
+    pub fn new(
+        name: &'static str,
+        marker: &str,
+        title: &str,
+        render: impl Fn() -> Result<Vec<String>, Error> + 'static,
+    ) -> Self {
+        Section {
+            name,
+            marker: marker.into(),
+            heading_level: 2,
+            title: title.into(),
+            render: Box::new(render),
+        }
+    }
+
+    /// Sets the heading level of the section.
+    #[must_use]
+    pub fn heading_level(mut self, heading_level: usize) -> Self {
+        self.heading_level = heading_level;
+        self
+    }
+
+    fn construct(&self) -> Result<String, Error> {
+        let mut lines: Vec<String> = vec![self.marker.clone()];
+
+        if self.heading_level > 0 {
+            lines.push(format!("{} {}", "#".repeat(self.heading_level), self.title));
+            lines.push(String::new());
+        }
+
+        lines.extend((self.render)()?);
+        lines.push(self.marker.clone());
+
+        Ok(lines.join("\n"))
+    }
+}
+
+struct SectionPosition {
+    pos_start: usize,
+    pos_end: usize,
+}
+
+fn locate_section(readme: &str, marker: &str) -> Result<SectionPosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
+
+    if matches.len() > 2 {
+        return Err(Error::Parser(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
+    }
+
+    let pos_start = matches
+        .first()
+        .map(|m| m.0)
+        .ok_or_else(|| Error::Parser(format!("{marker}: could not find start position.")))?;
+
+    let pos_end = matches
+        .last()
+        .map(|m| m.0 + m.1.len())
+        .ok_or_else(|| Error::Parser(format!("{marker}: could not find end position.")))?;
+
+    Ok(SectionPosition { pos_start, pos_end })
+}
+
+/// Replace a section in `s`. Errors if its markers are not present.
+pub fn update_content(s: &mut String, section: &Section) -> Result<(), Error> {
+    let positions = locate_section(s, &section.marker)?;
+    let content = section.construct()?;
+    s.replace_range(positions.pos_start..positions.pos_end, &content);
+    Ok(())
+}
+
+/// Replace a single section in the readme. Errors if its markers are not present.
+pub fn update_section(section: &Section) -> Result<(), Error> {
+    let mut readme = String::from_utf8_lossy(&fs::read(README_PATH)?).to_string();
+    update_content(&mut readme, section)?;
+    fs::write(README_PATH, &readme)?;
+    Ok(())
+}
+
+/// Replace every section whose markers are present in the readme.
+/// Returns the names of the updated sections.
+pub fn update_sections(sections: &[Section]) -> Result<Vec<&'static str>, Error> {
+    let mut readme = String::from_utf8_lossy(&fs::read(README_PATH)?).to_string();
+    let mut updated = vec![];
+
+    for section in sections {
+        if !readme.contains(&section.marker) {
+            continue;
+        }
+        update_content(&mut readme, section)?;
+        updated.push(section.name);
+    }
+
+    fs::write(README_PATH, &readme)?;
+    Ok(updated)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, Section};
+
+    static MARKER: &str = "<!--- test section --->";
+
+    fn get_mock_section() -> Section {
+        Section::new("test", MARKER, "Test", || {
+            Ok(vec!["line 1".into(), "line 2".into()])
+        })
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, &get_mock_section()).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_too_many_markers_present() {
+        let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
+        update_content(&mut s, &get_mock_section()).unwrap();
+    }
+
+    #[test]
+    fn updates_section() {
+        let mut s = format!("foo\n{}\nold\n{}\nbar", MARKER, MARKER);
+        update_content(&mut s, &get_mock_section()).unwrap();
+        let expected = [
+            "foo",
+            "<!--- test section --->",
+            "## Test",
+            "",
+            "line 1",
+            "line 2",
+            "<!--- test section --->",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn respects_heading_level() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, &get_mock_section().heading_level(3)).unwrap();
+        assert!(s.contains("\n### Test\n"));
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, &get_mock_section().heading_level(0)).unwrap();
+        assert_eq!(s, format!("{MARKER}\nline 1\nline 2\n{MARKER}"));
+    }
+
+    #[test]
+    fn updates_multiple_sections() {
+        let other_marker = "<!--- other section --->";
+        let other = Section::new("other", other_marker, "Other", || Ok(vec!["x".into()]));
+
+        let mut s = format!("{MARKER}{MARKER}\n{other_marker}{other_marker}");
+        update_content(&mut s, &get_mock_section()).unwrap();
+        update_content(&mut s, &other).unwrap();
+        update_content(&mut s, &get_mock_section()).unwrap();
+
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches(other_marker).count(), 2);
+        assert_eq!(s.matches("## Test").count(), 1);
+        assert!(s.contains("## Other\n\nx\n"));
+    }
+}
@@ -1,105 +1,107 @@
-/// Module that updates the readme me with timing information.
-/// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
-
-use crate::template::timings::Timings;
+/// Module that renders the benchmark section of the readme.
+use crate::template::readme::{self, Error, Section};
+use crate::template::timings::{Timing, Timings};
 use crate::template::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
-
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    Parser(String),
-    IO(io::Error),
-}
-
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
-    }
-}
-
-pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
-}
+static FOOTNOTE_PREFIX: &str = "bench-env";
 
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
-
-    if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
-    }
-
-    let pos_start = matches
-        .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
-
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
-
-    Ok(TablePosition { pos_start, pos_end })
-}
-
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
-
+fn construct_table(timings: &Timings, total_millis: f64, chart: Option<&str>) -> Vec<String> {
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
         "| Day | Part 1 | Part 2 |".into(),
         "| :---: | :---: | :---:  |".into(),
     ];
 
-    for timing in timings.data {
+    // NOTE: with a single environment, the footnote is attached to the total instead of every row.
+    let environments = timings.environments();
+    let footnote_for = |timing: &Timing| {
+        if environments.len() < 2 {
+            return String::new();
+        }
+        timing
+            .environment
+            .as_ref()
+            .and_then(|env| environments.iter().position(|e| *e == env))
+            .map(|i| format!("[^{FOOTNOTE_PREFIX}-{}]", i + 1))
+            .unwrap_or_default()
+    };
+
+    for timing in &timings.data {
         let path = get_path_for_bin(timing.day);
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}){} | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            footnote_for(timing),
+            timing.part_1.as_deref().unwrap_or("-"),
+            timing.part_2.as_deref().unwrap_or("-")
         ));
     }
 
     lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    if environments.len() == 1 {
+        lines.push(format!(
+            "**Total: {total_millis:.2}ms**[^{FOOTNOTE_PREFIX}-1]"
+        ));
+    } else {
+        lines.push(format!("**Total: {total_millis:.2}ms**"));
+    }
 
-    lines.join("\n")
+    if !environments.is_empty() {
+        lines.push(String::new());
+        for (i, environment) in environments.iter().enumerate() {
+            lines.push(format!(
+                "[^{FOOTNOTE_PREFIX}-{}]: Benchmarked on {environment}.",
+                i + 1
+            ));
+        }
+    }
+
+    if let Some(chart) = chart {
+        lines.push(String::new());
+        lines.push(format!("![Benchmark chart]({chart})"));
+    }
+
+    lines
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+fn build_section(timings: Timings, total_millis: f64, chart: Option<&str>) -> Section {
+    let chart = chart.map(String::from);
+    Section::new("benchmarks", MARKER, "Benchmarks", move || {
+        Ok(construct_table(&timings, total_millis, chart.as_deref()))
+    })
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+/// The benchmark section. If `chart` is set, the image at that path is embedded below the table.
+pub fn section(timings: Timings, chart: Option<&str>) -> Section {
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
-    Ok(())
+    build_section(timings, total_millis, chart)
+}
+
+#[cfg(feature = "test_lib")]
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    chart: Option<&str>,
+) -> Result<(), Error> {
+    readme::update_content(s, &build_section(timings, total_millis, chart))
+}
+
+/// Update the benchmark section in the readme.
+pub fn update(timings: Timings, chart: Option<&str>) -> Result<(), Error> {
+    readme::update_section(&section(timings, chart))
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
+    use crate::template::environment::tests::get_mock_environment;
     use crate::{day, template::timings::Timing, template::timings::Timings};
 
     fn get_mock_timings() -> Timings {
@@ -110,18 +112,24 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    environment: None,
+                    source_hash: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    environment: None,
+                    source_hash: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    environment: None,
+                    source_hash: None,
                 },
             ],
         }
@@ -131,28 +139,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +168,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -180,4 +188,54 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn footnotes_single_environment() {
+        let mut timings = get_mock_timings();
+        for timing in &mut timings.data {
+            timing.environment = Some(get_mock_environment());
+        }
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, None).unwrap();
+
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |"));
+        assert!(s.contains("**Total: 190.00ms**[^bench-env-1]"));
+        assert!(s.contains(&format!(
+            "[^bench-env-1]: Benchmarked on {}.",
+            get_mock_environment()
+        )));
+    }
+
+    #[test]
+    fn footnotes_multiple_environments() {
+        let mut timings = get_mock_timings();
+        let mut other_environment = get_mock_environment();
+        other_environment.cores = 4;
+        timings.data[0].environment = Some(get_mock_environment());
+        timings.data[1].environment = Some(other_environment);
+        timings.data[2].environment = Some(get_mock_environment());
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, None).unwrap();
+
+        assert!(s.contains("| [Day 1](./src/bin/01.rs)[^bench-env-1] |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs)[^bench-env-2] |"));
+        assert!(s.contains("| [Day 4](./src/bin/04.rs)[^bench-env-1] |"));
+        assert!(s.contains("**Total: 190.00ms**\n"));
+        assert!(s.contains("[^bench-env-2]: Benchmarked on Mock CPU 9000 (4 cores)"));
+    }
+
+    #[test]
+    fn embeds_chart() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            Some("./data/benchmarks.svg"),
+        )
+        .unwrap();
+        assert!(s.contains("**Total: 190.00ms**\n\n![Benchmark chart](./data/benchmarks.svg)\n"));
+    }
 }
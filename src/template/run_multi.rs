@@ -4,6 +4,8 @@ use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
+    environment::Environment,
+    source_hash,
     timings::{Timing, Timings},
 };
 
@@ -24,17 +26,25 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
+            // NOTE: hash before running so that edits made while benching are picked up next time.
+            let source_hash = source_hash::for_day(day);
             let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
 
             if output.is_empty() {
                 println!("Not solved.");
             } else {
-                let val = child_commands::parse_exec_time(&output, day);
+                let mut val = child_commands::parse_exec_time(&output, day);
+                val.source_hash = source_hash;
                 timings.push(val);
             }
         });
 
     if is_timed {
+        let environment = Environment::detect(is_release);
+        for timing in &mut timings {
+            timing.environment = Some(environment.clone());
+        }
+
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -134,6 +144,8 @@ pub mod child_commands {
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            environment: None,
+            source_hash: None,
         };
 
         output
@@ -179,14 +191,19 @@ pub mod child_commands {
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
+        let parsed_timing = parse_duration(str_timing)?;
+
+        Some((str_timing, parsed_timing))
+    }
+
+    /// Parse a duration formatted with `{:?}` (e.g. `74.13ms`) into nanoseconds.
+    pub fn parse_duration(s: &str) -> Option<f64> {
+        match s {
             s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
             s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
             s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
             s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        }
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -9,6 +9,21 @@ use std::{cmp, env, process};
 use crate::template::ANSI_BOLD;
 use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
 
+/// Parameters that control how often a solution part is executed when benching.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchSettings {
+    pub min_samples: u128,
+    pub max_samples: u128,
+    pub target: Duration,
+}
+
+/// The settings used by `cargo time`.
+pub const BENCH_SETTINGS: BenchSettings = BenchSettings {
+    min_samples: 10,
+    max_samples: 10000,
+    target: Duration::from_secs(1),
+};
+
 pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
@@ -24,7 +39,7 @@ pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, d
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched (approx. [`BENCH_SETTINGS`] target of execution time or its minimum samples, whatever take longer.)
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
@@ -58,8 +73,8 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let bench_iterations = (BENCH_SETTINGS.target.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(BENCH_SETTINGS.min_samples, BENCH_SETTINGS.max_samples);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -0,0 +1,147 @@
+/// Content hashes of the files a day's benchmark depends on.
+/// Used by `cargo time` to detect days whose stored timings are stale.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::template::Day;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Library modules that no solution depends on.
+const EXCLUDED_MODULES: [&str; 1] = ["template"];
+
+/// Paths that are hashed for a given day: the solution, the library and the input.
+fn get_source_paths(day: Day) -> Vec<PathBuf> {
+    let lib = PathBuf::from("./src/lib.rs");
+    let mut paths = vec![PathBuf::from(format!("./src/bin/{day}.rs")), lib.clone()];
+    collect_modules(&lib, &mut paths);
+    paths.push(PathBuf::from(format!("./data/inputs/{day}.txt")));
+    paths
+}
+
+/// Recursively collects the files of the modules that `file` declares, in declaration order.
+fn collect_modules(file: &Path, paths: &mut Vec<PathBuf>) {
+    let Ok(source) = fs::read_to_string(file) else {
+        return;
+    };
+    // NOTE: `lib.rs` and `mod.rs` keep their submodules next to them, `foo.rs` in `foo/`.
+    let dir = match file.file_stem().and_then(|stem| stem.to_str()) {
+        Some("lib" | "mod") => file.parent().map_or_else(PathBuf::new, Path::to_path_buf),
+        _ => file.with_extension(""),
+    };
+
+    for name in declared_modules(&source) {
+        if EXCLUDED_MODULES.contains(&name) {
+            continue;
+        }
+        let module = [
+            dir.join(format!("{name}.rs")),
+            dir.join(name).join("mod.rs"),
+        ]
+        .into_iter()
+        .find(|path| path.exists());
+        if let Some(module) = module {
+            paths.push(module.clone());
+            collect_modules(&module, paths);
+        }
+    }
+}
+
+/// Names of the modules declared as `mod name;` in `source`, ignoring inline modules.
+fn declared_modules(source: &str) -> Vec<&str> {
+    source
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let line = line.strip_prefix("pub ").unwrap_or(line);
+            let line = line.strip_prefix("pub(crate) ").unwrap_or(line);
+            line.strip_prefix("mod ")?.strip_suffix(';')
+        })
+        .map(str::trim)
+        .collect()
+}
+
+/// Hash the solution, library and input of a day.
+/// Returns `None` if the day has not been scaffolded yet.
+pub fn for_day(day: Day) -> Option<String> {
+    let paths = get_source_paths(day);
+
+    if !paths[0].exists() {
+        return None;
+    }
+
+    let mut hasher = Fnv1a::new();
+
+    for path in &paths {
+        hasher.write(path.to_string_lossy().as_bytes());
+        // NOTE: a missing file hashes differently than an empty one.
+        match fs::read(path) {
+            Ok(content) => {
+                hasher.write(&[1]);
+                hasher.write(&content);
+            }
+            Err(_) => hasher.write(&[0]),
+        }
+    }
+
+    Some(format!("{:016x}", hasher.finish()))
+}
+
+/// 64-bit FNV-1a. Unlike `DefaultHasher`, its output is stable across Rust releases.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Self(FNV_OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{declared_modules, get_source_paths, Fnv1a};
+    use crate::day;
+
+    fn hash(bytes: &[u8]) -> u64 {
+        let mut hasher = Fnv1a::new();
+        hasher.write(bytes);
+        hasher.finish()
+    }
+
+    #[test]
+    fn matches_reference_values() {
+        assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(hash(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn finds_declared_modules() {
+        let source = "pub mod grid;\nmod day;\npub(crate) mod queue;\n\n#[cfg(test)]\nmod tests {\n}\n// mod old;";
+        assert_eq!(declared_modules(source), ["grid", "day", "queue"]);
+    }
+
+    #[test]
+    fn hashes_library_modules_only() {
+        let paths = get_source_paths(day!(1));
+        assert_eq!(paths[0], PathBuf::from("./src/bin/01.rs"));
+        assert!(paths.contains(&PathBuf::from("./src/grid.rs")));
+        assert!(!paths.iter().any(|path| path.starts_with("./src/template")));
+        assert!(!paths.contains(&PathBuf::from("./src/main.rs")));
+    }
+}
@@ -1,6 +1,7 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
+use crate::template::environment::Environment;
 use crate::template::Day;
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
@@ -12,6 +13,8 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    pub environment: Option<Environment>,
+    pub source_hash: Option<String>,
 }
 
 /// Represents benchmark times for a set of days.
@@ -67,11 +70,36 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// Returns the stored timing for a day, if present.
+    pub fn get(&self, day: Day) -> Option<&Timing> {
+        self.data.iter().find(|t| t.day == day)
+    }
+
+    /// Returns the distinct benchmark environments in order of first appearance.
+    pub fn environments(&self) -> Vec<&Environment> {
+        let mut environments: Vec<&Environment> = vec![];
+        for environment in self.data.iter().filter_map(|t| t.environment.as_ref()) {
+            if !environments.contains(&environment) {
+                environments.push(environment);
+            }
+        }
+        environments
+    }
+
     pub fn is_day_complete(&self, day: Day) -> bool {
         self.data
             .iter()
             .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
     }
+
+    /// Checks if a day is fully benched and its sources have not changed since.
+    pub fn is_day_up_to_date(&self, day: Day, source_hash: Option<&str>) -> bool {
+        self.is_day_complete(day)
+            && source_hash.is_some()
+            && self
+                .get(day)
+                .is_some_and(|t| t.source_hash.as_deref() == source_hash)
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -140,6 +168,14 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        if let Some(environment) = &value.environment {
+            map.insert("environment".into(), JsonValue::from(environment));
+        }
+
+        if let Some(source_hash) = &value.source_hash {
+            map.insert("source_hash".into(), JsonValue::String(source_hash.clone()));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -173,11 +209,24 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: timings stored before environments were recorded do not have this key.
+        let environment = match json.get("environment") {
+            Some(v) if !v.is_null() => Some(Environment::try_from(v)?),
+            _ => None,
+        };
+
+        let source_hash = json
+            .get("source_hash")
+            .and_then(|v| v.get::<String>())
+            .cloned();
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            environment,
+            source_hash,
         })
     }
 }
@@ -198,18 +247,24 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    environment: None,
+                    source_hash: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    environment: None,
+                    source_hash: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    environment: None,
+                    source_hash: None,
                 },
             ],
         }
@@ -228,6 +283,17 @@ mod tests {
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.environment, None);
+        }
+
+        #[test]
+        fn handles_json_environments() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000, "environment": { "cpu_model": "Mock CPU", "cores": 4, "rustc_version": "rustc 1.83.0", "profile": "release", "target": "x86_64-linux", "target_features": ["sse2"], "bench": { "min_samples": 10, "max_samples": 10000, "target_nanos": 1000000000 } } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let environment = timings.data[0].environment.as_ref().unwrap();
+            assert_eq!(environment.cpu_model, "Mock CPU");
+            assert_eq!(environment.cores, 4);
+            assert_eq!(environment.target_features, vec!["sse2".to_string()]);
         }
 
         #[test]
@@ -289,10 +355,12 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    environment: None,
+                    source_hash: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -303,10 +371,12 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    environment: None,
+                    source_hash: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -317,10 +387,57 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    environment: None,
+                    source_hash: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod is_day_up_to_date {
+        use crate::{
+            day,
+            template::timings::{Timing, Timings},
+        };
+
+        fn get_timings(source_hash: Option<&str>) -> Timings {
+            Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    part_1: Some("1ms".into()),
+                    part_2: Some("2ms".into()),
+                    total_nanos: 3_000_000_000_f64,
+                    environment: None,
+                    source_hash: source_hash.map(String::from),
+                }],
+            }
+        }
+
+        #[test]
+        fn handles_unchanged_sources() {
+            let timings = get_timings(Some("abc"));
+            assert!(timings.is_day_up_to_date(day!(1), Some("abc")));
+        }
+
+        #[test]
+        fn handles_changed_sources() {
+            let timings = get_timings(Some("abc"));
+            assert!(!timings.is_day_up_to_date(day!(1), Some("def")));
+            assert!(!timings.is_day_up_to_date(day!(1), None));
+        }
+
+        #[test]
+        fn handles_timings_without_hash() {
+            let timings = get_timings(None);
+            assert!(!timings.is_day_up_to_date(day!(1), Some("abc")));
+        }
+
+        #[test]
+        fn handles_missing_days() {
+            let timings = get_timings(Some("abc"));
+            assert!(!timings.is_day_up_to_date(day!(2), Some("abc")));
         }
     }
 
@@ -341,6 +458,8 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    environment: None,
+                    source_hash: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -361,6 +480,8 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    environment: None,
+                    source_hash: None,
                 }],
             };
             let merged = timings.merge(&other);